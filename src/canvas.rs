@@ -1,24 +1,93 @@
-use crate::bag::Bag;
+use crate::constants::DEFAULT_PREVIEW_COUNT;
+use crate::high_scores::{HighScores, table_title};
+use crate::input::{Handling, KeyRepeat};
+use crate::personal_best::SprintRecord;
 use crate::playground::Playground;
-use crate::tetromino::Tetromino;
-use crate::types::{Matrix, TimeLocal};
+use crate::replay::{Playback, Recorder};
+use crate::types::TimeLocal;
+use iced::time::Instant;
 use iced::widget::canvas;
 use iced::widget::canvas::{Cache, Geometry};
 use iced::{Rectangle, Renderer, Theme, mouse};
+use std::path::PathBuf;
+use tetris_core::constants::DEFAULT_LOCK_DELAY_MS;
+use tetris_core::puzzle::Puzzle;
+use tetris_core::{Game, GameOptions, Mode};
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct State {
     pub now: TimeLocal,
     pub playground: Cache,
-    pub bag: Bag,
-    pub game_space: Matrix,
-    pub tick_rate_ms: u64,
-    pub level: u32,
-    pub rows_cleared: u32,
-    pub score: u32,
+    pub game: Game,
+    /// Seed from `--seed`; every new game reuses it. `None` picks a random seed per game.
+    pub seed: Option<u64>,
+    /// Board size, randomizer and mode for new games.
+    pub game_options: GameOptions,
+    pub preview_count: usize,
+    /// Lock delay from `--lock-delay`, used by every game that is started or resumed.
+    pub lock_delay_ms: u64,
+    /// Where the replay of a finished game is written, from `--record`.
+    pub record_path: Option<PathBuf>,
+    pub recorder: Option<Recorder>,
+    /// Set when watching a replay instead of playing.
+    pub playback: Option<Playback>,
+    pub high_scores: HighScores,
+    pub high_scores_path: Option<PathBuf>,
+    /// The fastest sprint so far, shown as splits while racing it.
+    pub personal_best: Option<SprintRecord>,
+    pub personal_best_path: Option<PathBuf>,
+    /// How much faster (negative) or slower than the personal best the last sprint finished.
+    pub best_split_ms: Option<i64>,
+    /// Set when the last sprint beat the previous personal best.
+    pub new_personal_best: bool,
+    /// Where a game in progress is saved on quit or with the save key.
+    pub save_path: Option<PathBuf>,
+    /// The name being typed after a game that made the high score table.
+    pub name_entry: Option<String>,
+    /// Rank of the entry added after the last game, highlighted in the table.
+    pub new_rank: Option<usize>,
+    /// Puzzles to pick from in puzzle mode: the bundled ones, or the one from `--puzzle`.
+    pub puzzles: Vec<Puzzle>,
+    pub puzzle_index: usize,
+    /// Set while a puzzle is being picked.
+    pub puzzle_menu: bool,
     pub is_running: bool,
-    pub game_over: bool,
-    pub next_item: Tetromino,
+    pub last_frame: Option<Instant>,
+    pub handling: Handling,
+    pub key_repeat: KeyRepeat,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            now: TimeLocal::default(),
+            playground: Cache::default(),
+            game: Game::default(),
+            seed: None,
+            game_options: GameOptions::default(),
+            preview_count: DEFAULT_PREVIEW_COUNT,
+            lock_delay_ms: DEFAULT_LOCK_DELAY_MS,
+            record_path: None,
+            recorder: None,
+            playback: None,
+            high_scores: HighScores::default(),
+            high_scores_path: None,
+            personal_best: None,
+            personal_best_path: None,
+            best_split_ms: None,
+            new_personal_best: false,
+            save_path: None,
+            name_entry: None,
+            new_rank: None,
+            puzzles: Vec::new(),
+            puzzle_index: 0,
+            puzzle_menu: false,
+            is_running: false,
+            last_frame: None,
+            handling: Handling::default(),
+            key_repeat: KeyRepeat::default(),
+        }
+    }
 }
 
 impl<Message> canvas::Program<Message> for State {
@@ -33,22 +102,63 @@ impl<Message> canvas::Program<Message> for State {
         _cursor: mouse::Cursor,
     ) -> Vec<Geometry> {
         let playground = self.playground.draw(renderer, bounds.size(), |frame| {
-            let half = bounds.width / 2.0;
+            let game = &self.game;
 
-            let mut playground = Playground::new(half);
+            let mut playground = Playground::new(bounds.size(), game.width(), game.height());
 
-            playground.render_level(frame, self.level);
-            playground.render_score(frame, self.score);
-            playground.render_next_brick(frame, &self.next_item);
+            playground.render_level(frame, game.level);
+            playground.render_score(frame, game.score);
+            if game.puzzle.is_none() {
+                playground.render_seed(frame, game.seed(), game.randomizer_kind());
+            }
+
+            playground.render_next_bricks(frame, game.preview(self.preview_count));
+            playground.render_hold_brick(frame, game.hold_item.as_ref());
+            playground.render_last_event(frame, game.last_event.as_ref());
+
+            match game.mode() {
+                Mode::Sprint => playground.render_timer(frame, game, self.personal_best.as_ref()),
+                Mode::Dig => playground.render_timer(frame, game, None),
+                Mode::Survival => playground.render_survival(frame, game),
+                Mode::Master => playground.render_master(frame, game),
+                Mode::Ultra => playground.render_countdown(frame, game),
+                Mode::Puzzle => playground.render_puzzle_goal(frame, game),
+                Mode::Marathon | Mode::Zen => {
+                    if let Some(goal) = game.options().line_goal() {
+                        playground.render_line_goal(frame, game.rows_cleared, goal);
+                    }
+                }
+            }
 
-            playground.render_bricks(frame, &self.game_space);
+            playground.render_bricks(frame, &game.game_space, game.active_piece.as_ref());
 
-            if !self.is_running {
+            if let Some(playback) = &self.playback {
+                playground.render_playback(frame, playback);
+            } else if self.puzzle_menu {
+                playground.render_puzzle_menu(frame, &self.puzzles, self.puzzle_index);
+            } else if !self.is_running {
                 playground.render_game_paused(frame);
             }
 
-            if self.game_over {
-                playground.render_game_over(frame);
+            if game.game_over && game.mode() == Mode::Puzzle {
+                playground.render_puzzle_result(frame, game);
+            } else if game.game_over && matches!(game.mode(), Mode::Sprint | Mode::Dig) {
+                playground.render_sprint_result(
+                    frame,
+                    game,
+                    self.personal_best.as_ref(),
+                    self.best_split_ms,
+                    self.new_personal_best,
+                );
+            } else if game.game_over {
+                playground.render_game_over(
+                    frame,
+                    game,
+                    &table_title(&game.options()),
+                    &self.high_scores,
+                    self.name_entry.as_deref(),
+                    self.new_rank,
+                );
             }
 
             playground.render_lines(frame);
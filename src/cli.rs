@@ -0,0 +1,328 @@
+use crate::constants::{DEFAULT_PREVIEW_COUNT, MAX_ARR_MS, MAX_DAS_MS, MAX_LOCK_DELAY_MS, MAX_SOFT_DROP_FACTOR};
+use crate::input::Handling;
+use std::path::PathBuf;
+use tetris_core::mode::MarathonGoal;
+use tetris_core::{Mode, RandomizerKind};
+use tetris_core::constants::{
+    BOARD_HEIGHT, BOARD_WIDTH, DEFAULT_DIG_ROWS, DEFAULT_LEVEL, DEFAULT_LOCK_DELAY_MS, DEFAULT_MESSINESS, DEFAULT_ULTRA_MINUTES,
+    MAX_BOARD_HEIGHT, MAX_BOARD_WIDTH, MAX_DIG_ROWS, MAX_MESSINESS, MAX_PREVIEW_COUNT, MAX_START_LEVEL,
+    MAX_ULTRA_MINUTES, MIN_BOARD_HEIGHT, MIN_BOARD_WIDTH, MIN_ULTRA_MINUTES,
+};
+
+pub const USAGE: &str = "Usage: tetris [--seed <number>] [--randomizer 7bag|14bag|random|nes|tgm1|tgm2] \
+[--preview 1-6] [--lock-delay 0-5000] [--das 0-1000] [--arr 0-500] [--sdf 1-100] [--width 4-40] [--height 4-100] [--mode marathon|sprint|ultra|dig|survival|master|zen|puzzle] [--goal 150|200|endless] [--level 0-29] [--minutes 2|3] \
+[--garbage 1-99] [--messiness 0-100] [--gravity on|off] [--puzzle <file>] \
+[--record <file>] [--replay <file>]";
+
+/// Options given on the command line. A fixed `seed` makes every game use the same piece
+/// sequence, which is handy for practice and for reproducing bugs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub seed: Option<u64>,
+    pub randomizer: RandomizerKind,
+    pub preview_count: usize,
+    /// Time a grounded piece waits before it locks.
+    pub lock_delay_ms: u64,
+    /// DAS, ARR and soft drop factor of held keys.
+    pub handling: Handling,
+    pub width: usize,
+    pub height: usize,
+    pub mode: Mode,
+    /// Lines that win a marathon.
+    pub marathon_goal: MarathonGoal,
+    /// Level a marathon starts at.
+    pub start_level: u32,
+    /// Length of an ultra game.
+    pub ultra_minutes: u32,
+    /// Garbage rows a dig starts with.
+    pub dig_rows: usize,
+    /// Chance in percent that a garbage hole of a dig or survival moves away from the one above
+    /// it.
+    pub messiness: u32,
+    /// Whether pieces fall on their own in zen.
+    pub gravity: bool,
+    /// Play this puzzle instead of picking one of the bundled puzzles.
+    pub puzzle: Option<PathBuf>,
+    /// Write the replay of every finished game to this file.
+    pub record: Option<PathBuf>,
+    /// Watch this replay instead of playing.
+    pub replay: Option<PathBuf>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            randomizer: RandomizerKind::default(),
+            preview_count: DEFAULT_PREVIEW_COUNT,
+            lock_delay_ms: DEFAULT_LOCK_DELAY_MS,
+            handling: Handling::default(),
+            width: BOARD_WIDTH,
+            height: BOARD_HEIGHT,
+            mode: Mode::default(),
+            marathon_goal: MarathonGoal::default(),
+            start_level: DEFAULT_LEVEL,
+            ultra_minutes: DEFAULT_ULTRA_MINUTES,
+            dig_rows: DEFAULT_DIG_ROWS,
+            messiness: DEFAULT_MESSINESS,
+            gravity: true,
+            puzzle: None,
+            record: None,
+            replay: None,
+        }
+    }
+}
+
+impl Options {
+    /// Parses the arguments after the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (name, value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+
+            let names = [
+                "--seed",
+                "--randomizer",
+                "--preview",
+                "--lock-delay",
+                "--das",
+                "--arr",
+                "--sdf",
+                "--width",
+                "--height",
+                "--mode",
+                "--goal",
+                "--level",
+                "--minutes",
+                "--garbage",
+                "--messiness",
+                "--gravity",
+                "--puzzle",
+                "--record",
+                "--replay",
+            ];
+
+            if !names.contains(&name.as_str()) {
+                return Err(format!("unknown argument '{}'", name));
+            }
+
+            let Some(value) = value.or_else(|| args.next()) else {
+                return Err(format!("{} needs a value", name));
+            };
+
+            match name.as_str() {
+                "--seed" => {
+                    let seed = value
+                        .parse()
+                        .map_err(|_| format!("invalid seed '{}', expected a number", value))?;
+                    options.seed = Some(seed);
+                }
+                "--randomizer" => options.randomizer = value.parse()?,
+                "--preview" => options.preview_count = parse_in_range("preview", &value, 1, MAX_PREVIEW_COUNT)?,
+                "--lock-delay" => {
+                    options.lock_delay_ms = parse_in_range("lock delay", &value, 0, MAX_LOCK_DELAY_MS as usize)? as u64
+                }
+                "--das" => options.handling.das_ms = parse_in_range("das", &value, 0, MAX_DAS_MS as usize)? as u64,
+                "--arr" => options.handling.arr_ms = parse_in_range("arr", &value, 0, MAX_ARR_MS as usize)? as u64,
+                "--sdf" => {
+                    options.handling.soft_drop_factor =
+                        parse_in_range("sdf", &value, 1, MAX_SOFT_DROP_FACTOR as usize)? as u64
+                }
+                "--width" => options.width = parse_in_range("width", &value, MIN_BOARD_WIDTH, MAX_BOARD_WIDTH)?,
+                "--height" => {
+                    options.height = parse_in_range("height", &value, MIN_BOARD_HEIGHT, MAX_BOARD_HEIGHT)?
+                }
+                "--mode" => options.mode = value.parse()?,
+                "--goal" => options.marathon_goal = value.parse()?,
+                "--level" => {
+                    options.start_level = parse_in_range("level", &value, 0, MAX_START_LEVEL as usize)? as u32
+                }
+                "--minutes" => {
+                    let minutes = parse_in_range("minutes", &value, MIN_ULTRA_MINUTES as usize, MAX_ULTRA_MINUTES as usize)?;
+                    options.ultra_minutes = minutes as u32;
+                }
+                "--garbage" => options.dig_rows = parse_in_range("garbage", &value, 1, MAX_DIG_ROWS)?,
+                "--messiness" => {
+                    options.messiness = parse_in_range("messiness", &value, 0, MAX_MESSINESS as usize)? as u32
+                }
+                "--gravity" => {
+                    options.gravity = match value.as_str() {
+                        "on" => true,
+                        "off" => false,
+                        _ => return Err(format!("invalid gravity '{}', expected on or off", value)),
+                    }
+                }
+                "--puzzle" => {
+                    options.mode = Mode::Puzzle;
+                    options.puzzle = Some(PathBuf::from(value));
+                }
+                "--record" => options.record = Some(PathBuf::from(value)),
+                "--replay" => options.replay = Some(PathBuf::from(value)),
+                _ => return Err(format!("unknown argument '{}'", name)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_in_range(name: &str, value: &str, min: usize, max: usize) -> Result<usize, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|number| (min..=max).contains(number))
+        .ok_or_else(|| format!("invalid {} '{}', expected {} to {}", name, value, min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_no_arguments() {
+        assert_eq!(parse(&[]), Ok(Options::default()));
+    }
+
+    #[test]
+    fn test_seed() {
+        assert_eq!(parse(&["--seed", "42"]).unwrap().seed, Some(42));
+        assert_eq!(parse(&["--seed=7"]).unwrap().seed, Some(7));
+    }
+
+    #[test]
+    fn test_randomizer() {
+        let options = parse(&["--randomizer", "tgm2", "--seed", "1"]).unwrap();
+
+        assert_eq!(options.randomizer, RandomizerKind::Tgm2);
+        assert_eq!(options.seed, Some(1));
+        assert_eq!(parse(&["--randomizer=nes"]).unwrap().randomizer, RandomizerKind::Nes);
+    }
+
+    #[test]
+    fn test_preview() {
+        assert_eq!(parse(&[]).unwrap().preview_count, DEFAULT_PREVIEW_COUNT);
+        assert_eq!(parse(&["--preview", "1"]).unwrap().preview_count, 1);
+        assert_eq!(parse(&["--preview=6"]).unwrap().preview_count, 6);
+    }
+
+    #[test]
+    fn test_lock_delay() {
+        assert_eq!(parse(&[]).unwrap().lock_delay_ms, DEFAULT_LOCK_DELAY_MS);
+        assert_eq!(parse(&["--lock-delay", "1000"]).unwrap().lock_delay_ms, 1000);
+        assert_eq!(parse(&["--lock-delay=0"]).unwrap().lock_delay_ms, 0);
+        assert!(parse(&["--lock-delay", "5001"]).is_err());
+        assert!(parse(&["--lock-delay", "fast"]).is_err());
+    }
+
+    #[test]
+    fn test_handling() {
+        assert_eq!(parse(&[]).unwrap().handling, Handling::default());
+
+        let handling = parse(&["--das", "100", "--arr=0", "--sdf", "40"]).unwrap().handling;
+        assert_eq!((handling.das_ms, handling.arr_ms, handling.soft_drop_factor), (100, 0, 40));
+
+        assert!(parse(&["--das", "1001"]).is_err());
+        assert!(parse(&["--arr", "-1"]).is_err());
+        assert!(parse(&["--sdf", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_board_size() {
+        let options = parse(&[]).unwrap();
+        assert_eq!((options.width, options.height), (BOARD_WIDTH, BOARD_HEIGHT));
+
+        let options = parse(&["--width", "4", "--height=40"]).unwrap();
+        assert_eq!((options.width, options.height), (4, 40));
+    }
+
+    #[test]
+    fn test_mode() {
+        assert_eq!(parse(&[]).unwrap().mode, Mode::Marathon);
+        assert_eq!(parse(&["--mode", "sprint"]).unwrap().mode, Mode::Sprint);
+        assert!(parse(&["--mode", "practice"]).is_err());
+
+        let options = parse(&["--mode", "ultra", "--minutes", "2"]).unwrap();
+        assert_eq!((options.mode, options.ultra_minutes), (Mode::Ultra, 2));
+        assert_eq!(parse(&["--mode=ultra"]).unwrap().ultra_minutes, DEFAULT_ULTRA_MINUTES);
+        assert!(parse(&["--minutes", "1"]).is_err());
+        assert!(parse(&["--minutes", "4"]).is_err());
+    }
+
+    #[test]
+    fn test_marathon_settings() {
+        let options = parse(&[]).unwrap();
+        assert_eq!((options.marathon_goal, options.start_level), (MarathonGoal::Endless, DEFAULT_LEVEL));
+
+        let options = parse(&["--goal", "150", "--level=18"]).unwrap();
+        assert_eq!((options.marathon_goal, options.start_level), (MarathonGoal::Lines150, 18));
+
+        assert!(parse(&["--goal", "100"]).is_err());
+        assert!(parse(&["--level", "30"]).is_err());
+    }
+
+    #[test]
+    fn test_dig_settings() {
+        let options = parse(&["--mode", "dig"]).unwrap();
+        assert_eq!((options.dig_rows, options.messiness), (DEFAULT_DIG_ROWS, DEFAULT_MESSINESS));
+
+        let options = parse(&["--mode=dig", "--garbage", "18", "--messiness=25"]).unwrap();
+        assert_eq!((options.mode, options.dig_rows, options.messiness), (Mode::Dig, 18, 25));
+
+        let options = parse(&["--mode", "survival", "--messiness", "0"]).unwrap();
+        assert_eq!((options.mode, options.messiness), (Mode::Survival, 0));
+
+        assert!(parse(&["--garbage", "0"]).is_err());
+        assert!(parse(&["--messiness", "101"]).is_err());
+    }
+
+    #[test]
+    fn test_zen_gravity() {
+        assert!(parse(&["--mode", "zen"]).unwrap().gravity);
+
+        let options = parse(&["--mode", "zen", "--gravity=off"]).unwrap();
+        assert_eq!((options.mode, options.gravity), (Mode::Zen, false));
+
+        assert!(parse(&["--gravity", "none"]).is_err());
+    }
+
+    #[test]
+    fn test_puzzle_file() {
+        let options = parse(&["--puzzle", "tsd.txt"]).unwrap();
+        assert_eq!((options.mode, options.puzzle), (Mode::Puzzle, Some(PathBuf::from("tsd.txt"))));
+
+        let options = parse(&["--mode=puzzle"]).unwrap();
+        assert_eq!((options.mode, options.puzzle), (Mode::Puzzle, None));
+    }
+
+    #[test]
+    fn test_replay_files() {
+        let options = parse(&["--record", "last.replay"]).unwrap();
+        assert_eq!(options.record, Some(PathBuf::from("last.replay")));
+
+        let options = parse(&["--replay=best.replay"]).unwrap();
+        assert_eq!(options.replay, Some(PathBuf::from("best.replay")));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(parse(&["--seed"]).is_err());
+        assert!(parse(&["--seed", "abc"]).is_err());
+        assert!(parse(&["--speed", "1"]).is_err());
+        assert!(parse(&["--randomizer", "8bag"]).is_err());
+        assert!(parse(&["--preview", "0"]).is_err());
+        assert!(parse(&["--preview", "7"]).is_err());
+        assert!(parse(&["--width", "3"]).is_err());
+        assert!(parse(&["--height", "101"]).is_err());
+        assert!(parse(&["--record"]).is_err());
+        assert_eq!(parse(&["--previews", "3"]).unwrap_err(), "unknown argument '--previews'");
+    }
+}
@@ -1,3 +1,7 @@
+use iced::Color;
+use tetris_core::enums::TetrominoKind;
+use tetris_core::tetromino::Brick;
+
 pub const WHITE_COLOR: [f32; 3] = [0.976, 0.980, 0.984];
 pub const BLACK_COLOR: [f32; 3] = [0.012, 0.027, 0.071];
 pub const PRIMARY_COLOR: [f32; 3] = [0.3, 0.6, 0.9];
@@ -11,3 +15,25 @@ pub const COLOR_Z: [f32; 3] = [1.0, 0.0, 0.0];
 pub const COLOR_T: [f32; 3] = [0.5, 0.0, 0.5];
 pub const COLOR_L: [f32; 3] = [1.0, 0.5, 0.0];
 pub const COLOR_J: [f32; 3] = [0.0, 0.0, 1.0];
+pub const GARBAGE_COLOR: [f32; 3] = [0.5, 0.5, 0.5];
+
+pub fn kind_color(kind: TetrominoKind) -> Color {
+    match kind {
+        TetrominoKind::O => COLOR_O.into(),
+        TetrominoKind::I => COLOR_I.into(),
+        TetrominoKind::S => COLOR_S.into(),
+        TetrominoKind::Z => COLOR_Z.into(),
+        TetrominoKind::T => COLOR_T.into(),
+        TetrominoKind::L => COLOR_L.into(),
+        TetrominoKind::J => COLOR_J.into(),
+    }
+}
+
+/// Color of a brick on the board; garbage is gray whatever its kind.
+pub fn brick_color(brick: &Brick) -> Color {
+    if brick.garbage {
+        GARBAGE_COLOR.into()
+    } else {
+        kind_color(brick.kind)
+    }
+}
@@ -1,8 +1,29 @@
+/// Largest square size; bigger boards shrink their squares to fit the window.
 pub const SQUARE_SIZE: f32 = 20.0;
-pub const NUM_OF_SQUARES_X: f32 = 10.0;
-pub const NUM_OF_SQUARES_Y: f32 = 20.0;
+pub const MIN_SQUARE_SIZE: f32 = 4.0;
 pub const SPACING: f32 = 1.0;
 pub const OFFSET_Y: f32 = 150.0;
-pub const PLAYGROUND_WIDTH: f32 = NUM_OF_SQUARES_X * SQUARE_SIZE + (NUM_OF_SQUARES_X - 1.0) * SPACING;
-pub const PLAYGROUND_HEIGHT: f32 = NUM_OF_SQUARES_Y * SQUARE_SIZE + (NUM_OF_SQUARES_Y - 1.0) * SPACING;
-pub const DEFAULT_LEVEL : u32 = 0;
\ No newline at end of file
+pub const BOTTOM_MARGIN: f32 = 30.0;
+/// Hidden rows drawn above the board so freshly spawned pieces can be seen.
+pub const SHOWN_BUFFER_ROWS: usize = 2;
+/// Room kept free on either side of the board for the hold and next panels.
+pub const SIDE_PANEL_WIDTH: f32 = 180.0;
+pub const HOLD_PANEL_WIDTH: f32 = 100.0;
+pub const GHOST_ALPHA: f32 = 0.3;
+pub const FRAME_RATE_MS: u64 = 16;
+pub const DEFAULT_DAS_MS: u64 = 167;
+pub const DEFAULT_ARR_MS: u64 = 33;
+pub const DEFAULT_SOFT_DROP_FACTOR: u64 = 20;
+/// Longest lock delay `--lock-delay` accepts.
+pub const MAX_LOCK_DELAY_MS: u64 = 5000;
+/// Upper bounds of the handling options.
+pub const MAX_DAS_MS: u64 = 1000;
+pub const MAX_ARR_MS: u64 = 500;
+pub const MAX_SOFT_DROP_FACTOR: u64 = 100;
+pub const DEFAULT_PREVIEW_COUNT: usize = 5;
+pub const PREVIEW_SCALE: f32 = 0.6;
+pub const PREVIEW_GAP: f32 = 10.0;
+pub const HIGH_SCORES_FILE: &str = "high_scores.txt";
+pub const SAVE_FILE: &str = "saved_game.txt";
+pub const SPRINT_PB_FILE: &str = "sprint_pb.txt";
+pub const HIGH_SCORE_TABLE_WIDTH: f32 = 420.0;
@@ -3,4 +3,5 @@ pub enum Direction {
     Left,
     Right,
     Bottom,
+    HardDrop,
 }
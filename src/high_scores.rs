@@ -0,0 +1,277 @@
+use crate::constants::HIGH_SCORES_FILE;
+use crate::storage::sized_file_name;
+use crate::types::TimeLocal;
+use chrono::{DateTime, Local};
+use std::io;
+use std::path::Path;
+use tetris_core::{GameOptions, Mode};
+
+pub const MAX_HIGH_SCORES: usize = 10;
+pub const MAX_NAME_LENGTH: usize = 12;
+const HEADER: &str = "tetris-high-scores 1";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HighScore {
+    pub name: String,
+    pub score: u32,
+    pub lines: u32,
+    pub level: u32,
+    pub date: TimeLocal,
+}
+
+/// The best games, highest score first.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HighScores {
+    pub entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// Whether `score` would make it into the table.
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < MAX_HIGH_SCORES
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// Inserts `entry` below every entry with the same or a higher score and returns its rank,
+    /// or `None` when it did not make the table.
+    pub fn insert(&mut self, entry: HighScore) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+
+        let rank = self
+            .entries
+            .iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(self.entries.len());
+
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_HIGH_SCORES);
+        Some(rank)
+    }
+
+    /// One entry per line after a header: score, lines, level, RFC 3339 date and name,
+    /// separated by tabs.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+
+        for entry in &self.entries {
+            text.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                entry.score,
+                entry.lines,
+                entry.level,
+                entry.date.to_rfc3339(),
+                entry.name
+            ));
+        }
+
+        text
+    }
+
+    /// Reads the table back, skipping lines that cannot be parsed.
+    pub fn from_text(text: &str) -> io::Result<Self> {
+        let mut lines = text.lines();
+
+        if lines.next() != Some(HEADER) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a high score file"));
+        }
+
+        let mut high_scores = HighScores::default();
+
+        for line in lines {
+            if let Some(entry) = parse_entry(line) {
+                high_scores.insert(entry);
+            }
+        }
+
+        Ok(high_scores)
+    }
+
+    /// Loads the table, treating a missing file as an empty table.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::from_text(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Like [`HighScores::load`], but reports a table that cannot be read and starts empty.
+    pub fn load_or_default(path: &Path) -> Self {
+        Self::load(path).unwrap_or_else(|error| {
+            eprintln!("Could not load the high scores from {}: {}", path.display(), error);
+            HighScores::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        std::fs::write(path, self.to_text())
+    }
+}
+
+/// Scores are only compared within a category: marathon games share one table, every ultra
+/// length has its own and so do survival and master, each once per board size.
+pub fn table_file(options: &GameOptions) -> String {
+    let file = match options.mode {
+        Mode::Ultra => format!("high_scores_ultra_{}min.txt", options.ultra_minutes),
+        Mode::Survival => "high_scores_survival.txt".to_string(),
+        Mode::Master => "high_scores_master.txt".to_string(),
+        _ => HIGH_SCORES_FILE.to_string(),
+    };
+
+    sized_file_name(&file, options.width, options.height)
+}
+
+/// Heading of the table for games played with `options`.
+pub fn table_title(options: &GameOptions) -> String {
+    match options.mode {
+        Mode::Ultra => format!("Ultra {} min high scores", options.ultra_minutes),
+        Mode::Survival => "Survival high scores".to_string(),
+        Mode::Master => "Master high scores".to_string(),
+        _ => "High scores".to_string(),
+    }
+}
+
+fn parse_entry(line: &str) -> Option<HighScore> {
+    let mut fields = line.splitn(5, '\t');
+
+    let score = fields.next()?.parse().ok()?;
+    let lines = fields.next()?.parse().ok()?;
+    let level = fields.next()?.parse().ok()?;
+    let date = DateTime::parse_from_rfc3339(fields.next()?).ok()?;
+    let name = fields.next()?;
+
+    Some(HighScore {
+        name: clean_name(name),
+        score,
+        lines,
+        level,
+        date: date.with_timezone(&Local),
+    })
+}
+
+/// Drops control characters, trims and shortens a player name, falling back to "Player".
+pub fn clean_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .filter(|character| !character.is_control())
+        .take(MAX_NAME_LENGTH)
+        .collect();
+    let name = name.trim();
+
+    if name.is_empty() {
+        "Player".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: u32) -> HighScore {
+        HighScore {
+            name: name.to_string(),
+            score,
+            lines: score / 100,
+            level: score / 1000,
+            date: DateTime::parse_from_rfc3339("2025-06-01T18:30:00+02:00")
+                .unwrap()
+                .with_timezone(&Local),
+        }
+    }
+
+    #[test]
+    fn test_insert_keeps_top_ten_in_order() {
+        let mut high_scores = HighScores::default();
+
+        for score in 1..=12 {
+            high_scores.insert(entry("a", score * 100));
+        }
+
+        assert_eq!(high_scores.entries.len(), MAX_HIGH_SCORES);
+        assert_eq!(high_scores.entries[0].score, 1200);
+        assert_eq!(high_scores.entries[9].score, 300);
+
+        assert!(!high_scores.qualifies(300));
+        assert_eq!(high_scores.insert(entry("b", 300)), None);
+        assert_eq!(high_scores.insert(entry("c", 650)), Some(6));
+        assert_eq!(high_scores.entries[9].score, 400);
+    }
+
+    #[test]
+    fn test_ties_rank_below_older_entries() {
+        let mut high_scores = HighScores::default();
+        high_scores.insert(entry("first", 500));
+
+        assert_eq!(high_scores.insert(entry("second", 500)), Some(1));
+    }
+
+    #[test]
+    fn test_zero_never_qualifies() {
+        assert!(!HighScores::default().qualifies(0));
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let mut high_scores = HighScores::default();
+        high_scores.insert(entry("Alex", 4200));
+        high_scores.insert(entry("Sam Lee", 900));
+
+        assert_eq!(HighScores::from_text(&high_scores.to_text()).unwrap(), high_scores);
+    }
+
+    #[test]
+    fn test_bad_lines_are_skipped() {
+        let text = format!(
+            "{}\nnot\ta\tscore\n{}\t1\t0\t2025-06-01T18:30:00+02:00\tAlex\n",
+            HEADER, 100
+        );
+
+        let high_scores = HighScores::from_text(&text).unwrap();
+        assert_eq!(high_scores.entries.len(), 1);
+        assert_eq!(high_scores.entries[0].name, "Alex");
+
+        assert!(HighScores::from_text("something else").is_err());
+    }
+
+    #[test]
+    fn test_clean_name() {
+        assert_eq!(clean_name("  Alex\t"), "Alex");
+        assert_eq!(clean_name(""), "Player");
+        assert_eq!(clean_name("abcdefghijklmnop"), "abcdefghijkl");
+    }
+
+    #[test]
+    fn test_categories_have_their_own_tables() {
+        let marathon = GameOptions::default();
+        let ultra = |ultra_minutes| GameOptions {
+            mode: Mode::Ultra,
+            ultra_minutes,
+            ..GameOptions::default()
+        };
+
+        assert_eq!(table_file(&marathon), HIGH_SCORES_FILE);
+        assert_ne!(table_file(&ultra(2)), table_file(&marathon));
+        assert_ne!(table_file(&ultra(2)), table_file(&ultra(3)));
+
+        let survival = GameOptions {
+            mode: Mode::Survival,
+            ..GameOptions::default()
+        };
+        assert_ne!(table_file(&survival), table_file(&marathon));
+
+        let narrow = GameOptions {
+            width: 4,
+            ..GameOptions::default()
+        };
+        assert_eq!(table_file(&narrow), "high_scores_4x20.txt");
+    }
+}
@@ -0,0 +1,224 @@
+use crate::constants::{DEFAULT_ARR_MS, DEFAULT_DAS_MS, DEFAULT_SOFT_DROP_FACTOR};
+use crate::enums::Direction;
+
+/// How held keys repeat. `das_ms` is the delay before a held left/right key starts repeating,
+/// `arr_ms` the interval between repeats (0 shifts straight to the wall) and
+/// `soft_drop_factor` how many times faster than gravity a held soft drop moves the piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Handling {
+    pub das_ms: u64,
+    pub arr_ms: u64,
+    pub soft_drop_factor: u64,
+}
+
+impl Default for Handling {
+    fn default() -> Self {
+        Self {
+            das_ms: DEFAULT_DAS_MS,
+            arr_ms: DEFAULT_ARR_MS,
+            soft_drop_factor: DEFAULT_SOFT_DROP_FACTOR,
+        }
+    }
+}
+
+/// Which movement keys are held and for how long, so repeats do not depend on the OS.
+#[derive(Debug, Default)]
+pub struct KeyRepeat {
+    left_held: bool,
+    right_held: bool,
+    shift: Option<Direction>,
+    shift_ms: u64,
+    soft_drop_held: bool,
+    soft_drop_ms: u64,
+    hard_drop_held: bool,
+}
+
+impl KeyRepeat {
+    /// Registers a key press and returns the move to apply right away. OS key repeats of a key
+    /// that is already held return `None`.
+    pub fn press(&mut self, dir: Direction) -> Option<Direction> {
+        let held = match dir {
+            Direction::Left => &mut self.left_held,
+            Direction::Right => &mut self.right_held,
+            Direction::Bottom => &mut self.soft_drop_held,
+            Direction::HardDrop => &mut self.hard_drop_held,
+        };
+
+        if *held {
+            return None;
+        }
+
+        *held = true;
+
+        match dir {
+            Direction::Left | Direction::Right => {
+                self.shift = Some(dir);
+                self.shift_ms = 0;
+            }
+            Direction::Bottom => self.soft_drop_ms = 0,
+            Direction::HardDrop => {}
+        }
+
+        Some(dir)
+    }
+
+    pub fn release(&mut self, dir: Direction) {
+        match dir {
+            Direction::Left => {
+                self.left_held = false;
+                self.release_shift(Direction::Left, self.right_held, Direction::Right);
+            }
+            Direction::Right => {
+                self.right_held = false;
+                self.release_shift(Direction::Right, self.left_held, Direction::Left);
+            }
+            Direction::Bottom => self.soft_drop_held = false,
+            Direction::HardDrop => self.hard_drop_held = false,
+        }
+    }
+
+    /// Lets `elapsed_ms` pass and returns the repeated moves that became due. With no ARR a
+    /// shift takes `board_width` moves, enough to reach the wall from anywhere.
+    pub fn advance(
+        &mut self,
+        elapsed_ms: u64,
+        handling: &Handling,
+        gravity_ms: u64,
+        board_width: usize,
+    ) -> Vec<Direction> {
+        let mut moves = Vec::new();
+
+        if let Some(dir) = self.shift {
+            let before = self.shift_ms;
+            self.shift_ms += elapsed_ms;
+
+            // Without DAS the first frame after the press already counts as past it.
+            let count = if handling.arr_ms == 0 {
+                if (before < handling.das_ms || before == 0) && self.shift_ms >= handling.das_ms {
+                    board_width
+                } else {
+                    0
+                }
+            } else {
+                let repeats = |ms: u64| {
+                    if ms < handling.das_ms {
+                        0
+                    } else {
+                        (ms - handling.das_ms) / handling.arr_ms + 1
+                    }
+                };
+
+                (repeats(self.shift_ms) - repeats(before)) as usize
+            };
+
+            moves.extend(std::iter::repeat_n(dir, count));
+        }
+
+        if self.soft_drop_held {
+            let interval = (gravity_ms / handling.soft_drop_factor.max(1)).max(1);
+            let before = self.soft_drop_ms;
+            self.soft_drop_ms += elapsed_ms;
+
+            let count = self.soft_drop_ms / interval - before / interval;
+            moves.extend(std::iter::repeat_n(Direction::Bottom, count as usize));
+        }
+
+        moves
+    }
+
+    fn release_shift(&mut self, released: Direction, other_held: bool, other: Direction) {
+        if self.shift == Some(released) {
+            self.shift = other_held.then_some(other);
+            self.shift_ms = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HANDLING: Handling = Handling {
+        das_ms: 100,
+        arr_ms: 20,
+        soft_drop_factor: 10,
+    };
+
+    #[test]
+    fn test_press_moves_once_and_ignores_os_repeat() {
+        let mut keys = KeyRepeat::default();
+
+        assert_eq!(keys.press(Direction::Left), Some(Direction::Left));
+        assert_eq!(keys.press(Direction::Left), None);
+
+        keys.release(Direction::Left);
+        assert_eq!(keys.press(Direction::Left), Some(Direction::Left));
+    }
+
+    #[test]
+    fn test_das_then_arr() {
+        let mut keys = KeyRepeat::default();
+        keys.press(Direction::Right);
+
+        assert!(keys.advance(99, &HANDLING, 800, 10).is_empty());
+        assert_eq!(keys.advance(1, &HANDLING, 800, 10), vec![Direction::Right]);
+        assert!(keys.advance(19, &HANDLING, 800, 10).is_empty());
+        assert_eq!(keys.advance(40, &HANDLING, 800, 10), vec![Direction::Right; 2]);
+
+        keys.release(Direction::Right);
+        assert!(keys.advance(100, &HANDLING, 800, 10).is_empty());
+    }
+
+    #[test]
+    fn test_zero_arr_shifts_to_the_wall() {
+        let handling = Handling {
+            arr_ms: 0,
+            ..HANDLING
+        };
+        let mut keys = KeyRepeat::default();
+        keys.press(Direction::Left);
+
+        assert!(keys.advance(50, &handling, 800, 10).is_empty());
+        assert_eq!(keys.advance(50, &handling, 800, 10), vec![Direction::Left; 10]);
+        assert!(keys.advance(50, &handling, 800, 10).is_empty());
+    }
+
+    #[test]
+    fn test_zero_das_and_arr_shift_to_the_wall_at_once() {
+        let handling = Handling {
+            das_ms: 0,
+            arr_ms: 0,
+            ..HANDLING
+        };
+        let mut keys = KeyRepeat::default();
+        keys.press(Direction::Right);
+
+        assert_eq!(keys.advance(16, &handling, 800, 10), vec![Direction::Right; 10]);
+        assert!(keys.advance(16, &handling, 800, 10).is_empty());
+    }
+
+    #[test]
+    fn test_last_pressed_direction_wins() {
+        let mut keys = KeyRepeat::default();
+        keys.press(Direction::Left);
+        keys.press(Direction::Right);
+
+        assert_eq!(keys.advance(100, &HANDLING, 800, 10), vec![Direction::Right]);
+
+        keys.release(Direction::Right);
+        assert!(keys.advance(99, &HANDLING, 800, 10).is_empty());
+        assert_eq!(keys.advance(1, &HANDLING, 800, 10), vec![Direction::Left]);
+    }
+
+    #[test]
+    fn test_soft_drop_factor() {
+        let mut keys = KeyRepeat::default();
+
+        assert_eq!(keys.press(Direction::Bottom), Some(Direction::Bottom));
+        assert!(keys.advance(79, &HANDLING, 800, 10).is_empty());
+        assert_eq!(keys.advance(81, &HANDLING, 800, 10), vec![Direction::Bottom; 2]);
+
+        keys.release(Direction::Bottom);
+        assert!(keys.advance(800, &HANDLING, 800, 10).is_empty());
+    }
+}
@@ -1,36 +1,81 @@
-mod bag;
 mod canvas;
+mod cli;
 mod colors;
 mod constants;
 mod enums;
-mod level;
-mod moves;
+mod high_scores;
+mod input;
+mod personal_best;
 mod playground;
-mod rotations;
-mod tetromino;
+mod puzzles;
+mod replay;
+mod saved_game;
+mod storage;
 mod subscription;
 mod types;
 mod update;
 mod view;
 
 use crate::canvas::State;
+use crate::cli::{Options, USAGE};
+use crate::high_scores::{HighScores, table_file};
+use crate::personal_best::{SprintRecord, record_file};
 use crate::colors::{BLACK_COLOR, DANGER_COLOR, PRIMARY_COLOR, SECONDARY_COLOR, WHITE_COLOR};
+use crate::constants::SAVE_FILE;
+use crate::replay::{Playback, Replay};
 use crate::subscription::subscription;
 use crate::update::{Message, update};
 use crate::view::view;
 use iced::theme::{Custom, Palette};
 use iced::{Task, Theme};
 use std::sync::Arc;
-use crate::constants::DEFAULT_LEVEL;
-use crate::level::get_speed_by_level;
+use tetris_core::GameOptions;
+use tetris_core::puzzle::Puzzle;
 
-fn init() -> (State, Task<Message>) {
+fn init(options: Options, replay: Option<Replay>, puzzles: Vec<Puzzle>) -> (State, Task<Message>) {
     tracing_subscriber::fmt::init();
 
+    let game_options = GameOptions {
+        width: options.width,
+        height: options.height,
+        randomizer: options.randomizer,
+        mode: options.mode,
+        ultra_minutes: options.ultra_minutes,
+        dig_rows: options.dig_rows,
+        messiness: options.messiness,
+        gravity: options.gravity,
+        marathon_goal: options.marathon_goal,
+        start_level: options.start_level,
+    };
+
+    let high_scores_path = storage::data_file(&table_file(&game_options));
+    let high_scores = high_scores_path
+        .as_deref()
+        .map_or_else(HighScores::default, HighScores::load_or_default);
+
+    let personal_best_path = storage::data_file(&record_file(&game_options));
+    let personal_best = personal_best_path.as_ref().and_then(|path| {
+        SprintRecord::load(path).unwrap_or_else(|error| {
+            eprintln!("Could not load the sprint record from {}: {}", path.display(), error);
+            None
+        })
+    });
+
     let state = State {
-        tick_rate_ms: get_speed_by_level(DEFAULT_LEVEL),
-        level: DEFAULT_LEVEL,
-        ..Default::default()
+        seed: options.seed,
+        game_options,
+        preview_count: options.preview_count,
+        lock_delay_ms: options.lock_delay_ms,
+        handling: options.handling,
+        record_path: options.record,
+        playback: replay.map(Playback::new),
+        high_scores,
+        high_scores_path,
+        personal_best,
+        personal_best_path,
+        save_path: storage::data_file(SAVE_FILE),
+        puzzles,
+        ..State::default()
     };
     (state, Task::perform(async {}, |_| Message::Initialize))
 }
@@ -51,8 +96,39 @@ fn theme(_: &State) -> Theme {
 }
 
 fn main() -> iced::Result {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let replay = match &options.replay {
+        Some(path) => match Replay::load(path) {
+            Ok(replay) => Some(replay),
+            Err(error) => {
+                eprintln!("Could not load the replay {}: {}", path.display(), error);
+                std::process::exit(2);
+            }
+        },
+        None => None,
+    };
+
+    let puzzles = match &options.puzzle {
+        Some(path) => match puzzles::load(path) {
+            Ok(puzzle) => vec![puzzle],
+            Err(error) => {
+                eprintln!("Could not load the puzzle {}: {}", path.display(), error);
+                std::process::exit(2);
+            }
+        },
+        None => puzzles::bundled(),
+    };
+
     iced::application("Tetris", update, view)
         .theme(theme)
         .subscription(subscription)
-        .run_with(init)
+        .exit_on_close_request(false)
+        .run_with(move || init(options, replay, puzzles))
 }
@@ -0,0 +1,181 @@
+use crate::constants::SPRINT_PB_FILE;
+use crate::storage::sized_file_name;
+use std::io;
+use std::path::Path;
+use tetris_core::{Game, GameOptions};
+
+const HEADER: &str = "tetris-sprint-pb 1";
+
+/// The fastest finished sprint, with the time each line was cleared so a later run can be
+/// compared against it line by line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SprintRecord {
+    pub time_ms: u64,
+    pub pieces: u32,
+    pub finesse_faults: u32,
+    pub line_times: Vec<u64>,
+}
+
+impl SprintRecord {
+    /// The record of a finished sprint.
+    pub fn from_game(game: &Game) -> Self {
+        Self {
+            time_ms: game.elapsed_ms,
+            pieces: game.pieces_placed,
+            finesse_faults: game.finesse_faults,
+            line_times: game.line_times.clone(),
+        }
+    }
+
+    /// How far behind (positive) or ahead (negative) of this record `game` is, in milliseconds,
+    /// at the last line both have cleared.
+    pub fn split_ms(&self, game: &Game) -> Option<i64> {
+        let line = game.line_times.len().min(self.line_times.len()).checked_sub(1)?;
+
+        Some(game.line_times[line] as i64 - self.line_times[line] as i64)
+    }
+
+    /// A header, then the time, piece count and finesse faults, then the line times, all
+    /// separated by spaces.
+    pub fn to_text(&self) -> String {
+        let line_times: Vec<String> = self.line_times.iter().map(|time| time.to_string()).collect();
+
+        format!(
+            "{}\n{} {} {}\n{}\n",
+            HEADER,
+            self.time_ms,
+            self.pieces,
+            self.finesse_faults,
+            line_times.join(" ")
+        )
+    }
+
+    pub fn from_text(text: &str) -> io::Result<Self> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "not a sprint record file");
+        let mut lines = text.lines();
+
+        if lines.next() != Some(HEADER) {
+            return Err(invalid());
+        }
+
+        let numbers: Vec<u64> = lines
+            .next()
+            .ok_or_else(invalid)?
+            .split_whitespace()
+            .map(|number| number.parse().map_err(|_| invalid()))
+            .collect::<io::Result<_>>()?;
+        let [time_ms, pieces, finesse_faults] = numbers[..] else {
+            return Err(invalid());
+        };
+
+        let line_times = lines
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(|time| time.parse().map_err(|_| invalid()))
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            time_ms,
+            pieces: pieces as u32,
+            finesse_faults: finesse_faults as u32,
+            line_times,
+        })
+    }
+
+    /// Loads the record, treating a missing file as no record yet.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::from_text(&text).map(Some),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        std::fs::write(path, self.to_text())
+    }
+}
+
+/// Sprints on every board size race their own personal best.
+pub fn record_file(options: &GameOptions) -> String {
+    sized_file_name(SPRINT_PB_FILE, options.width, options.height)
+}
+
+/// Formats milliseconds as `m:ss.mmm`.
+pub fn format_time(time_ms: u64) -> String {
+    format!("{}:{:02}.{:03}", time_ms / 60_000, time_ms / 1000 % 60, time_ms % 1000)
+}
+
+/// Formats a split as `+s.mmm` or `-s.mmm`.
+pub fn format_split(split_ms: i64) -> String {
+    let sign = if split_ms > 0 { '+' } else if split_ms < 0 { '-' } else { '±' };
+    let split_ms = split_ms.unsigned_abs();
+
+    format!("{}{}.{:03}", sign, split_ms / 1000, split_ms % 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> SprintRecord {
+        SprintRecord {
+            time_ms: 61_250,
+            pieces: 101,
+            finesse_faults: 3,
+            line_times: vec![1500, 3000, 3000, 4250],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let record = record();
+
+        assert_eq!(SprintRecord::from_text(&record.to_text()).unwrap(), record);
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        assert!(SprintRecord::from_text("").is_err());
+        assert!(SprintRecord::from_text("tetris-high-scores 1\n").is_err());
+        assert!(SprintRecord::from_text("tetris-sprint-pb 1\n1 2\n").is_err());
+        assert!(SprintRecord::from_text("tetris-sprint-pb 1\n1 2 3\nx\n").is_err());
+    }
+
+    #[test]
+    fn test_split_at_last_common_line() {
+        let mut game = Game::with_seed(0);
+        assert_eq!(record().split_ms(&game), None);
+
+        game.line_times = vec![1000, 3500];
+        assert_eq!(record().split_ms(&game), Some(500));
+
+        game.line_times = vec![1000, 2000, 2500, 3000, 3100];
+        assert_eq!(record().split_ms(&game), Some(-1250));
+    }
+
+    #[test]
+    fn test_board_sizes_have_their_own_record() {
+        let tall = GameOptions {
+            height: 40,
+            ..GameOptions::default()
+        };
+
+        assert_eq!(record_file(&GameOptions::default()), SPRINT_PB_FILE);
+        assert_eq!(record_file(&tall), "sprint_pb_10x40.txt");
+    }
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(format_time(61_250), "1:01.250");
+        assert_eq!(format_time(7), "0:00.007");
+        assert_eq!(format_split(1250), "+1.250");
+        assert_eq!(format_split(-40), "-0.040");
+        assert_eq!(format_split(0), "±0.000");
+    }
+}
@@ -1,31 +1,457 @@
-use iced::{Color, Pixels, Point, Renderer, Size};
+use iced::{Color, Font, Pixels, Point, Renderer, Size};
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::canvas::{Frame, Path, Stroke, Text};
-use crate::colors::WHITE_COLOR;
-use crate::constants::{OFFSET_Y, PLAYGROUND_HEIGHT, PLAYGROUND_WIDTH, SPACING, SQUARE_SIZE};
-use crate::tetromino::{Tetromino};
-use crate::types::Matrix;
+use crate::colors::{DANGER_COLOR, SECONDARY_COLOR, WHITE_COLOR, brick_color, kind_color};
+use crate::high_scores::HighScores;
+use crate::personal_best::{SprintRecord, format_split, format_time};
+use crate::replay::Playback;
+use crate::constants::{BOTTOM_MARGIN, SHOWN_BUFFER_ROWS, GHOST_ALPHA, HIGH_SCORE_TABLE_WIDTH, HOLD_PANEL_WIDTH, MIN_SQUARE_SIZE, OFFSET_Y, PREVIEW_GAP, PREVIEW_SCALE, SIDE_PANEL_WIDTH, SPACING, SQUARE_SIZE};
+use tetris_core::constants::{BUFFER_HEIGHT, GRAVITY_UNIT};
+use tetris_core::master::section_stop;
+use tetris_core::{Game, Mode, RandomizerKind, TopOut};
+use tetris_core::enums::TetrominoKind;
+use tetris_core::moves::drop_distance;
+use tetris_core::piece::ActivePiece;
+use tetris_core::puzzle::Puzzle;
+use tetris_core::scoring::ScoreEvent;
+use tetris_core::tetromino::Tetromino;
+use tetris_core::types::Matrix;
+
+/// Green when ahead of the personal best, red when behind.
+fn split_color(split_ms: i64) -> Color {
+    if split_ms <= 0 {
+        SECONDARY_COLOR.into()
+    } else {
+        DANGER_COLOR.into()
+    }
+}
 
 pub struct Playground {
     half_width: f32,
+    square_size: f32,
+    width: f32,
+    height: f32,
+}
+
+/// Largest square size up to `SQUARE_SIZE` that fits a `cols` by `rows` board into `bounds`
+/// with room for the side panels.
+pub fn square_size(bounds: Size, cols: usize, rows: usize) -> f32 {
+    let fit = |space: f32, squares: usize| (space + SPACING) / squares as f32 - SPACING;
+
+    let by_width = fit(bounds.width - 2.0 * SIDE_PANEL_WIDTH, cols);
+    let by_height = fit(bounds.height - OFFSET_Y - BOTTOM_MARGIN, rows);
+
+    by_width.min(by_height).clamp(MIN_SQUARE_SIZE, SQUARE_SIZE)
 }
 
 impl Playground {
-    pub fn new(half_width: f32) -> Self {
+    /// Lays out a `cols` by `rows` board centered in `bounds`.
+    pub fn new(bounds: Size, cols: usize, rows: usize) -> Self {
+        let square_size = square_size(bounds, cols, rows);
+        let extent = |squares: usize| squares as f32 * square_size + (squares as f32 - 1.0) * SPACING;
+
         Self {
-            half_width,
+            half_width: bounds.width / 2.0,
+            square_size,
+            width: extent(cols),
+            height: extent(rows),
         }
     }
     
-    pub fn render_game_over(&self, frame: &mut Frame<Renderer>) {
+    /// Draws "Game over" and how the game topped out, "Time up" when its clock ran out or
+    /// "Victory" when it reached its goal, with the high score table titled `title`, and the
+    /// name prompt when the last game made it into the table.
+    pub fn render_game_over(
+        &self,
+        frame: &mut Frame<Renderer>,
+        game: &Game,
+        title: &str,
+        high_scores: &HighScores,
+        name_entry: Option<&str>,
+        new_rank: Option<usize>,
+    ) {
+        let content = match game.top_out {
+            _ if game.completed && game.mode() == Mode::Ultra => "Time up".to_string(),
+            _ if game.completed => format!("Victory! {} lines cleared", game.rows_cleared),
+            Some(TopOut::BlockOut) => "Game over - block out".to_string(),
+            Some(TopOut::LockOut) => "Game over - lock out".to_string(),
+            Some(TopOut::PushOut) => "Game over - pushed out".to_string(),
+            None => "Game over".to_string(),
+        };
+
         frame.fill_text(Text {
-            content: "Game over".into(),
+            content,
             position: Point {
-                x: self.half_width - 50.0,
-                y: self.half_width - OFFSET_Y - 100.0,
+                x: self.half_width,
+                y: OFFSET_Y + 30.0,
             },
             color: WHITE_COLOR.into(),
             size: Pixels(20.0),
+            horizontal_alignment: Horizontal::Center,
+            vertical_alignment: Vertical::Center,
+            ..Default::default()
+        });
+
+        let top = OFFSET_Y + 70.0;
+        let row_height = 20.0;
+        let rows = high_scores.entries.len().max(1) as f32 + 2.0;
+
+        frame.fill_rectangle(
+            Point::new(self.half_width - HIGH_SCORE_TABLE_WIDTH / 2.0, top - 10.0),
+            Size {
+                width: HIGH_SCORE_TABLE_WIDTH,
+                height: rows * row_height + 20.0,
+            },
+            Color::from_rgba(0.0, 0.0, 0.0, 0.85),
+        );
+
+        let title = match name_entry {
+            Some(name) => format!("New high score! Name: {}_", name),
+            None => title.to_string(),
+        };
+        self.render_table_row(frame, &title, top, WHITE_COLOR.into());
+
+        if high_scores.entries.is_empty() {
+            self.render_table_row(frame, "No scores yet", top + row_height * 2.0, WHITE_COLOR.into());
+        }
+
+        for (rank, entry) in high_scores.entries.iter().enumerate() {
+            let content = format!(
+                "{:>2}. {:<12} {:>7}  {:>3} lines  lvl {:>2}  {}",
+                rank + 1,
+                entry.name,
+                entry.score,
+                entry.lines,
+                entry.level,
+                entry.date.format("%Y-%m-%d")
+            );
+            let color = if Some(rank) == new_rank {
+                kind_color(TetrominoKind::O)
+            } else {
+                WHITE_COLOR.into()
+            };
+
+            self.render_table_row(frame, &content, top + row_height * (rank as f32 + 2.0), color);
+        }
+    }
+
+    /// Draws how a sprint or dig ended: why it failed or how far ahead of the personal best a
+    /// sprint finished, with its time, pieces per second and finesse faults.
+    pub fn render_sprint_result(
+        &self,
+        frame: &mut Frame<Renderer>,
+        game: &Game,
+        personal_best: Option<&SprintRecord>,
+        best_split_ms: Option<i64>,
+        new_personal_best: bool,
+    ) {
+        let top = OFFSET_Y + 70.0;
+        let row_height = 20.0;
+
+        frame.fill_rectangle(
+            Point::new(self.half_width - HIGH_SCORE_TABLE_WIDTH / 2.0, top - 10.0),
+            Size {
+                width: HIGH_SCORE_TABLE_WIDTH,
+                height: 7.0 * row_height + 20.0,
+            },
+            Color::from_rgba(0.0, 0.0, 0.0, 0.85),
+        );
+
+        let name = if game.mode() == Mode::Dig { "Dig" } else { "Sprint" };
+        let title = match game.top_out {
+            _ if game.completed && new_personal_best => format!("{} complete - new personal best!", name),
+            _ if game.completed => format!("{} complete", name),
+            Some(TopOut::BlockOut) => format!("{} failed - block out", name),
+            Some(TopOut::LockOut) => format!("{} failed - lock out", name),
+            Some(TopOut::PushOut) => format!("{} failed - pushed out", name),
+            None => format!("{} failed", name),
+        };
+        self.render_table_row(frame, &title, top, WHITE_COLOR.into());
+
+        let seconds = game.elapsed_ms as f32 / 1000.0;
+        let pieces_per_second = if seconds > 0.0 {
+            game.pieces_placed as f32 / seconds
+        } else {
+            0.0
+        };
+
+        let rows = [
+            format!("Time      {}", format_time(game.elapsed_ms)),
+            format!("Lines     {}", game.rows_cleared),
+            format!("Pieces    {} ({:.2} per second)", game.pieces_placed, pieces_per_second),
+            format!("Finesse   {} faults", game.finesse_faults),
+        ];
+
+        for (index, content) in rows.iter().enumerate() {
+            self.render_table_row(frame, content, top + row_height * (index as f32 + 2.0), WHITE_COLOR.into());
+        }
+
+        if game.mode() == Mode::Dig {
+            let garbage = format!("Garbage   {} left", game.garbage_remaining());
+            self.render_table_row(frame, &garbage, top + row_height * 6.0, WHITE_COLOR.into());
+            return;
+        }
+
+        let best = personal_best.map_or("-".to_string(), |best| format_time(best.time_ms));
+        self.render_table_row(frame, &format!("Best      {}", best), top + row_height * 6.0, WHITE_COLOR.into());
+
+        if let Some(split_ms) = best_split_ms {
+            self.render_table_row(
+                frame,
+                &format!("vs best   {}", format_split(split_ms)),
+                top + row_height * 7.0,
+                split_color(split_ms),
+            );
+        }
+    }
+
+    /// Lines cleared towards a marathon's goal, below the hold piece.
+    pub fn render_line_goal(&self, frame: &mut Frame<Renderer>, rows_cleared: u32, goal: u32) {
+        frame.fill_text(Text {
+            content: format!("Lines: {}/{}", rows_cleared.min(goal), goal),
+            position: Point {
+                x: self.half_width - self.width / 2.0 - HOLD_PANEL_WIDTH,
+                y: OFFSET_Y + 160.0,
+            },
+            color: WHITE_COLOR.into(),
+            size: Pixels(14.0),
+            horizontal_alignment: Horizontal::Left,
+            vertical_alignment: Vertical::Center,
+            ..Default::default()
+        });
+    }
+
+    /// The time left in a game played against the clock, below the hold piece.
+    pub fn render_countdown(&self, frame: &mut Frame<Renderer>, game: &Game) {
+        let limit = game.options().time_limit_ms().unwrap_or_default();
+        let remaining_ms = limit.saturating_sub(game.elapsed_ms);
+
+        // The last ten seconds are shown in red.
+        let color = if remaining_ms <= 10_000 {
+            DANGER_COLOR.into()
+        } else {
+            WHITE_COLOR.into()
+        };
+
+        frame.fill_text(Text {
+            content: format_time(remaining_ms),
+            position: Point {
+                x: self.half_width - self.width / 2.0 - HOLD_PANEL_WIDTH,
+                y: OFFSET_Y + 160.0,
+            },
+            color,
+            size: Pixels(18.0),
+            font: Font::MONOSPACE,
+            horizontal_alignment: Horizontal::Left,
+            vertical_alignment: Vertical::Center,
+            ..Default::default()
+        });
+    }
+
+    /// The master clock, the level the current section stops at and the gravity in G, below
+    /// the hold piece.
+    pub fn render_master(&self, frame: &mut Frame<Renderer>, game: &Game) {
+        let x = self.half_width - self.width / 2.0 - HOLD_PANEL_WIDTH;
+        let gravity = game.gravity().unwrap_or_default() as f32 / GRAVITY_UNIT as f32;
+
+        let rows = [
+            (format_time(game.elapsed_ms), 18.0),
+            (format!("Level: {}/{}", game.level, section_stop(game.level) + 1), 14.0),
+            (format!("Gravity: {:.2}G", gravity), 14.0),
+        ];
+
+        for (index, (content, size)) in rows.into_iter().enumerate() {
+            frame.fill_text(Text {
+                content,
+                position: Point {
+                    x,
+                    y: OFFSET_Y + 160.0 + index as f32 * 22.0,
+                },
+                color: WHITE_COLOR.into(),
+                size: Pixels(size),
+                font: Font::MONOSPACE,
+                horizontal_alignment: Horizontal::Left,
+                vertical_alignment: Vertical::Center,
+                ..Default::default()
+            });
+        }
+    }
+
+    /// How long a survival has lasted and the time until the next garbage row rises, in red
+    /// for the last second, below the hold piece.
+    pub fn render_survival(&self, frame: &mut Frame<Renderer>, game: &Game) {
+        let x = self.half_width - self.width / 2.0 - HOLD_PANEL_WIDTH;
+        let next_garbage_ms = game.next_garbage_ms().unwrap_or_default();
+
+        let garbage_color = if next_garbage_ms <= 1_000 {
+            DANGER_COLOR.into()
+        } else {
+            WHITE_COLOR.into()
+        };
+
+        let rows = [
+            (format_time(game.elapsed_ms), 18.0, Color::from(WHITE_COLOR)),
+            (
+                format!("Garbage in {}.{}s", next_garbage_ms / 1000, next_garbage_ms % 1000 / 100),
+                14.0,
+                garbage_color,
+            ),
+        ];
+
+        for (index, (content, size, color)) in rows.into_iter().enumerate() {
+            frame.fill_text(Text {
+                content,
+                position: Point {
+                    x,
+                    y: OFFSET_Y + 160.0 + index as f32 * 22.0,
+                },
+                color,
+                size: Pixels(size),
+                font: Font::MONOSPACE,
+                horizontal_alignment: Horizontal::Left,
+                vertical_alignment: Vertical::Center,
+                ..Default::default()
+            });
+        }
+    }
+
+    /// A puzzle's name, goal and the pieces still to come, below the hold piece.
+    pub fn render_puzzle_goal(&self, frame: &mut Frame<Renderer>, game: &Game) {
+        let Some(puzzle) = &game.puzzle else {
+            return;
+        };
+
+        let x = self.half_width - self.width / 2.0 - HOLD_PANEL_WIDTH;
+
+        let rows = [
+            (puzzle.name.clone(), 14.0),
+            (puzzle.goal.description(), 14.0),
+            (format!("Pieces: {}", game.pieces_left().unwrap_or_default()), 14.0),
+        ];
+
+        for (index, (content, size)) in rows.into_iter().enumerate() {
+            frame.fill_text(Text {
+                content,
+                position: Point {
+                    x,
+                    y: OFFSET_Y + 160.0 + index as f32 * 22.0,
+                },
+                color: WHITE_COLOR.into(),
+                size: Pixels(size),
+                font: Font::MONOSPACE,
+                horizontal_alignment: Horizontal::Left,
+                vertical_alignment: Vertical::Center,
+                ..Default::default()
+            });
+        }
+    }
+
+    /// Draws whether the puzzle was solved, or why it failed.
+    pub fn render_puzzle_result(&self, frame: &mut Frame<Renderer>, game: &Game) {
+        let content = match game.top_out {
+            _ if game.completed => "Puzzle solved!",
+            Some(TopOut::BlockOut) => "Puzzle failed - block out",
+            Some(TopOut::LockOut) => "Puzzle failed - lock out",
+            Some(TopOut::PushOut) => "Puzzle failed - pushed out",
+            None => "Puzzle failed - out of pieces",
+        };
+
+        let top = OFFSET_Y + 70.0;
+        let row_height = 20.0;
+
+        frame.fill_rectangle(
+            Point::new(self.half_width - HIGH_SCORE_TABLE_WIDTH / 2.0, top - 10.0),
+            Size {
+                width: HIGH_SCORE_TABLE_WIDTH,
+                height: 3.0 * row_height + 20.0,
+            },
+            Color::from_rgba(0.0, 0.0, 0.0, 0.85),
+        );
+
+        self.render_table_row(frame, content, top, WHITE_COLOR.into());
+        self.render_table_row(frame, "Press space to continue", top + row_height * 2.0, WHITE_COLOR.into());
+    }
+
+    /// The bundled puzzles with their goals, `selected` highlighted.
+    pub fn render_puzzle_menu(&self, frame: &mut Frame<Renderer>, puzzles: &[Puzzle], selected: usize) {
+        let top = OFFSET_Y + 70.0;
+        let row_height = 20.0;
+
+        frame.fill_rectangle(
+            Point::new(self.half_width - HIGH_SCORE_TABLE_WIDTH / 2.0, top - 10.0),
+            Size {
+                width: HIGH_SCORE_TABLE_WIDTH,
+                height: (puzzles.len() as f32 + 4.0) * row_height + 20.0,
+            },
+            Color::from_rgba(0.0, 0.0, 0.0, 0.85),
+        );
+
+        self.render_table_row(frame, "Puzzles", top, WHITE_COLOR.into());
+
+        for (index, puzzle) in puzzles.iter().enumerate() {
+            let content = format!("{:>2}. {:<20} {}", index + 1, puzzle.name, puzzle.goal.description());
+            let color = if index == selected {
+                kind_color(TetrominoKind::O)
+            } else {
+                WHITE_COLOR.into()
+            };
+
+            self.render_table_row(frame, &content, top + row_height * (index as f32 + 2.0), color);
+        }
+
+        self.render_table_row(
+            frame,
+            "Up/down to choose, space to start",
+            top + row_height * (puzzles.len() as f32 + 3.0),
+            WHITE_COLOR.into(),
+        );
+    }
+
+    /// The clock and what is left to do: lines to clear and the split against the personal
+    /// best at the last cleared line in a sprint, garbage rows in a dig. Below the hold piece.
+    pub fn render_timer(&self, frame: &mut Frame<Renderer>, game: &Game, personal_best: Option<&SprintRecord>) {
+        let x = self.half_width - self.width / 2.0 - HOLD_PANEL_WIDTH;
+
+        let progress = match game.options().line_goal() {
+            Some(goal) => format!("Lines: {}/{}", game.rows_cleared.min(goal), goal),
+            None => format!("Garbage: {} left", game.garbage_remaining()),
+        };
+        let mut rows = vec![
+            (format_time(game.elapsed_ms), 18.0, Color::from(WHITE_COLOR)),
+            (progress, 14.0, WHITE_COLOR.into()),
+        ];
+
+        if let Some(split_ms) = personal_best.and_then(|best| best.split_ms(game)) {
+            rows.push((format_split(split_ms), 14.0, split_color(split_ms)));
+        }
+
+        for (index, (content, size, color)) in rows.into_iter().enumerate() {
+            frame.fill_text(Text {
+                content,
+                position: Point {
+                    x,
+                    y: OFFSET_Y + 160.0 + index as f32 * 22.0,
+                },
+                color,
+                size: Pixels(size),
+                font: Font::MONOSPACE,
+                horizontal_alignment: Horizontal::Left,
+                vertical_alignment: Vertical::Center,
+                ..Default::default()
+            });
+        }
+    }
+
+    fn render_table_row(&self, frame: &mut Frame<Renderer>, content: &str, y: f32, color: Color) {
+        frame.fill_text(Text {
+            content: content.to_string(),
+            position: Point {
+                x: self.half_width - HIGH_SCORE_TABLE_WIDTH / 2.0 + 10.0,
+                y,
+            },
+            color,
+            size: Pixels(13.0),
+            font: Font::MONOSPACE,
             horizontal_alignment: Horizontal::Left,
             vertical_alignment: Vertical::Center,
             ..Default::default()
@@ -36,7 +462,7 @@ impl Playground {
         frame.fill_text(Text {
             content: format!("Level: {}", level),
             position: Point {
-                x: self.half_width + PLAYGROUND_WIDTH / 2.0 + 10.0,
+                x: self.half_width + self.width / 2.0 + 10.0,
                 y: OFFSET_Y - 20.0,
             },
             color: WHITE_COLOR.into(),
@@ -51,7 +477,7 @@ impl Playground {
         frame.fill_text(Text {
             content: format!("Score: {}", score),
             position: Point {
-                x: self.half_width + PLAYGROUND_WIDTH / 2.0 + 10.0,
+                x: self.half_width + self.width / 2.0 + 10.0,
                 y: OFFSET_Y,
             },
             color: WHITE_COLOR.into(),
@@ -62,11 +488,68 @@ impl Playground {
         });
     }
 
-    pub fn render_next_brick(&self, frame: &mut Frame<Renderer>, next_item: &Tetromino) {
+    pub fn render_seed(&self, frame: &mut Frame<Renderer>, seed: u64, randomizer: RandomizerKind) {
         frame.fill_text(Text {
-            content: "Next brick".to_string(),
+            content: format!("Seed: {} ({})", seed, randomizer.name()),
             position: Point {
-                x: self.half_width + PLAYGROUND_WIDTH / 2.0 + 10.0,
+                x: self.half_width + self.width / 2.0 + 10.0,
+                y: self.height + OFFSET_Y - 10.0,
+            },
+            color: WHITE_COLOR.into(),
+            size: Pixels(12.0),
+            horizontal_alignment: Horizontal::Left,
+            vertical_alignment: Vertical::Center,
+            ..Default::default()
+        });
+    }
+
+    /// Draws the upcoming pieces top to bottom; the first one at full size, the rest smaller.
+    pub fn render_next_bricks<'a>(
+        &self,
+        frame: &mut Frame<Renderer>,
+        next_items: impl Iterator<Item = &'a Tetromino>,
+    ) {
+        frame.fill_text(Text {
+            content: "Next".to_string(),
+            position: Point {
+                x: self.half_width + self.width / 2.0 + 10.0,
+                y: OFFSET_Y + 20.0,
+            },
+            color: WHITE_COLOR.into(),
+            size: Pixels(14.0),
+            horizontal_alignment: Horizontal::Left,
+            vertical_alignment: Vertical::Center,
+            ..Default::default()
+        });
+
+        let mut y = OFFSET_Y + 30.0;
+
+        for (index, next_item) in next_items.enumerate() {
+            let square_size = if index == 0 {
+                self.square_size
+            } else {
+                self.square_size * PREVIEW_SCALE
+            };
+
+            let height = self.render_tetromino(
+                frame,
+                next_item,
+                Point {
+                    x: self.half_width + self.width / 2.0 + 20.0,
+                    y,
+                },
+                square_size,
+            );
+
+            y += height + PREVIEW_GAP;
+        }
+    }
+
+    pub fn render_hold_brick(&self, frame: &mut Frame<Renderer>, hold_item: Option<&Tetromino>) {
+        frame.fill_text(Text {
+            content: "Hold".to_string(),
+            position: Point {
+                x: self.half_width - self.width / 2.0 - HOLD_PANEL_WIDTH,
                 y: OFFSET_Y + 20.0,
             },
             color: WHITE_COLOR.into(),
@@ -76,42 +559,99 @@ impl Playground {
             ..Default::default()
         });
 
-        let offset = 30.0;
+        if let Some(item) = hold_item {
+            self.render_tetromino(
+                frame,
+                item,
+                Point {
+                    x: self.half_width - self.width / 2.0 - HOLD_PANEL_WIDTH,
+                    y: OFFSET_Y + 30.0,
+                },
+                self.square_size,
+            );
+        }
+    }
+
+    pub fn render_last_event(&self, frame: &mut Frame<Renderer>, event: Option<&ScoreEvent>) {
+        let Some(label) = event.and_then(|event| event.label()) else {
+            return;
+        };
+
+        frame.fill_text(Text {
+            content: label,
+            position: Point {
+                x: self.half_width - self.width / 2.0 - 10.0,
+                y: OFFSET_Y + 120.0,
+            },
+            color: WHITE_COLOR.into(),
+            size: Pixels(14.0),
+            horizontal_alignment: Horizontal::Right,
+            vertical_alignment: Vertical::Center,
+            ..Default::default()
+        });
+    }
+
+    /// Draws the filled rows of `item` with squares of `square_size` and returns the height used.
+    fn render_tetromino(
+        &self,
+        frame: &mut Frame<Renderer>,
+        item: &Tetromino,
+        position: Point,
+        square_size: f32,
+    ) -> f32 {
         let mut row_index = 0;
 
-        for (_, row) in next_item.matrix.iter().enumerate() {
+        for row in item.matrix.iter() {
             let has_brick = row.iter().any(|cell| cell.is_some());
-            
+
             if !has_brick {
                 continue;
             }
 
-            for (col_index, item) in row.iter().enumerate() {
-                if let Some(brick) = item {
+            for (col_index, cell) in row.iter().enumerate() {
+                if let Some(brick) = cell {
                     let rect = Path::rectangle(
                         Point {
-                            x: col_index as f32 * SQUARE_SIZE
-                                + self.half_width
-                                + PLAYGROUND_WIDTH / 2.0
-                                + 20.0
-                                + col_index as f32 * SPACING,
-                            y: row_index as f32 * SQUARE_SIZE
-                                + OFFSET_Y
-                                + offset
-                                + row_index as f32 * SPACING,
+                            x: position.x + col_index as f32 * (square_size + SPACING),
+                            y: position.y + row_index as f32 * (square_size + SPACING),
                         },
                         Size {
-                            width: SQUARE_SIZE,
-                            height: SQUARE_SIZE,
+                            width: square_size,
+                            height: square_size,
                         },
                     );
 
-                    frame.fill(&rect, brick.color);
+                    frame.fill(&rect, kind_color(brick.kind));
                 }
             }
 
             row_index += 1;
         }
+
+        row_index as f32 * (square_size + SPACING)
+    }
+
+    pub fn render_playback(&self, frame: &mut Frame<Renderer>, playback: &Playback) {
+        let content = if playback.is_finished() {
+            "Replay finished".to_string()
+        } else if playback.paused {
+            "Replay paused - Space resume, → step frame".to_string()
+        } else {
+            format!("Replay {}x - Space pause, ↑/↓ speed", playback.speed())
+        };
+
+        frame.fill_text(Text {
+            content,
+            position: Point {
+                x: self.half_width,
+                y: OFFSET_Y - 50.0,
+            },
+            color: WHITE_COLOR.into(),
+            size: Pixels(14.0),
+            horizontal_alignment: Horizontal::Center,
+            vertical_alignment: Vertical::Center,
+            ..Default::default()
+        });
     }
 
     pub fn render_game_paused(&self, frame: &mut Frame<Renderer>) {
@@ -142,32 +682,32 @@ impl Playground {
         let lines = [
             (
                 Point {
-                    x: self.half_width - PLAYGROUND_WIDTH / 2.0,
+                    x: self.half_width - self.width / 2.0,
                     y: OFFSET_Y,
                 },
                 Point {
-                    x: self.half_width - PLAYGROUND_WIDTH / 2.0,
-                    y: PLAYGROUND_HEIGHT + OFFSET_Y,
+                    x: self.half_width - self.width / 2.0,
+                    y: self.height + OFFSET_Y,
                 },
             ),
             (
                 Point {
-                    x: self.half_width + PLAYGROUND_WIDTH / 2.0,
+                    x: self.half_width + self.width / 2.0,
                     y: OFFSET_Y,
                 },
                 Point {
-                    x: self.half_width + PLAYGROUND_WIDTH / 2.0,
-                    y: PLAYGROUND_HEIGHT + OFFSET_Y,
+                    x: self.half_width + self.width / 2.0,
+                    y: self.height + OFFSET_Y,
                 },
             ),
             (
                 Point {
-                    x: self.half_width - PLAYGROUND_WIDTH / 2.0,
-                    y: PLAYGROUND_HEIGHT + OFFSET_Y,
+                    x: self.half_width - self.width / 2.0,
+                    y: self.height + OFFSET_Y,
                 },
                 Point {
-                    x: self.half_width + PLAYGROUND_WIDTH / 2.0,
-                    y: PLAYGROUND_HEIGHT + OFFSET_Y,
+                    x: self.half_width + self.width / 2.0,
+                    y: self.height + OFFSET_Y,
                 },
             ),
         ];
@@ -178,79 +718,56 @@ impl Playground {
         }
     }
     
-    pub fn render_bricks(&self, frame: &mut Frame<Renderer>, game_space: &Matrix) {
-        let offset_x = self.half_width - PLAYGROUND_WIDTH / 2.0;
-
-        for (row_index, row) in game_space.iter().enumerate() {
-            for (col_index, cell) in row.iter().enumerate() {
-                if let Some(brick) = cell {
-                    let x = offset_x + col_index as f32 * (SQUARE_SIZE + SPACING);
-                    let y = OFFSET_Y + row_index as f32 * (SQUARE_SIZE + SPACING);
-
-                    let rect = Path::rectangle(
-                        Point { x, y },
-                        Size {
-                            width: SQUARE_SIZE,
-                            height: SQUARE_SIZE,
-                        },
-                    );
-
-                    frame.fill(&rect, brick.color);
-                }
-            }
-        }
-    }
+    pub fn render_bricks(
+        &self,
+        frame: &mut Frame<Renderer>,
+        game_space: &Matrix,
+        active_piece: Option<&ActivePiece>,
+    ) {
+        let offset_x = self.half_width - self.width / 2.0;
 
-    pub fn clear_rows(game_space: &mut Matrix, score: &mut u32) -> u32 {
-        let rows = game_space.len();
-        let cols = game_space[0].len();
+        let square_size = self.square_size;
 
-        let mut rows_to_clear = Vec::new();
+        // Rows are drawn relative to the top of the visible board; only the spawn rows of the
+        // hidden buffer are shown above it.
+        let first_shown = BUFFER_HEIGHT as isize - SHOWN_BUFFER_ROWS as isize;
+        let brick_rect = |row_index: isize, col_index: isize| {
+            let x = offset_x + col_index as f32 * (square_size + SPACING);
+            let y = OFFSET_Y + (row_index - BUFFER_HEIGHT as isize) as f32 * (square_size + SPACING);
 
-        for row in 0..rows {
-            let mut is_complete = true;
+            Path::rectangle(
+                Point { x, y },
+                Size {
+                    width: square_size,
+                    height: square_size,
+                },
+            )
+        };
 
-            for col in 0..cols {
-                if game_space[row][col].is_none()
-                    || (game_space[row][col].is_some() && game_space[row][col].unwrap().moving)
-                {
-                    is_complete = false;
-                    break;
+        for (row_index, row) in game_space.iter().enumerate().skip(first_shown as usize) {
+            for (col_index, cell) in row.iter().enumerate() {
+                if let Some(brick) = cell {
+                    let rect = brick_rect(row_index as isize, col_index as isize);
+                    frame.fill(&rect, brick_color(brick));
                 }
             }
-
-            if is_complete {
-                rows_to_clear.push(row);
-            }
         }
 
-        if !rows_to_clear.is_empty() {
-            let points = match rows_to_clear.len() {
-                1 => 100,
-                2 => 300,
-                3 => 500,
-                4 => 800,
-                n => n * 100,
-            };
+        if let Some(piece) = active_piece {
+            let color = kind_color(piece.kind);
+            let ghost = piece.shifted(0, drop_distance(game_space, piece) as isize);
 
-            *score += points as u32;
-
-            for &row_to_clear in rows_to_clear.iter().rev() {
-                for col in 0..cols {
-                    game_space[row_to_clear][col] = None;
+            for (row_index, col_index) in ghost.cells() {
+                if row_index >= first_shown {
+                    frame.fill(&brick_rect(row_index, col_index), Color { a: GHOST_ALPHA, ..color });
                 }
+            }
 
-                for row in (1..=row_to_clear).rev() {
-                    for col in 0..cols {
-                        game_space[row][col] = game_space[row - 1][col].take();
-                    }
+            for (row_index, col_index) in piece.cells() {
+                if row_index >= first_shown {
+                    frame.fill(&brick_rect(row_index, col_index), color);
                 }
             }
-
-            return rows_to_clear.len() as u32;
         }
-
-        0
     }
-
-}
\ No newline at end of file
+}
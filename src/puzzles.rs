@@ -0,0 +1,33 @@
+use std::path::Path;
+use tetris_core::puzzle::Puzzle;
+
+/// The puzzles shipped with the game, in the order they are offered.
+const BUNDLED: [&str; 4] = [
+    include_str!("../puzzles/01_first_tetris.txt"),
+    include_str!("../puzzles/02_hold_on.txt"),
+    include_str!("../puzzles/03_perfect_clear.txt"),
+    include_str!("../puzzles/04_t_spin_double.txt"),
+];
+
+pub fn bundled() -> Vec<Puzzle> {
+    BUNDLED.iter().map(|text| text.parse().unwrap()).collect()
+}
+
+/// Reads a puzzle file, such as one from `--puzzle`.
+pub fn load(path: &Path) -> Result<Puzzle, String> {
+    std::fs::read_to_string(path)
+        .map_err(|error| error.to_string())?
+        .parse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_puzzles_parse() {
+        for text in BUNDLED {
+            assert!(text.parse::<Puzzle>().is_ok(), "{}", text);
+        }
+    }
+}
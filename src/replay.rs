@@ -0,0 +1,749 @@
+use crate::enums::Direction;
+use crate::update::Message;
+use iced::time::Instant;
+use std::io;
+use std::path::Path;
+use tetris_core::constants::{
+    DEFAULT_LOCK_DELAY_MS, MAX_BOARD_HEIGHT, MAX_BOARD_WIDTH, MAX_DIG_ROWS, MAX_MESSINESS, MAX_START_LEVEL, MAX_ULTRA_MINUTES,
+    MIN_BOARD_HEIGHT, MIN_BOARD_WIDTH, MIN_ULTRA_MINUTES,
+};
+use tetris_core::mode::MarathonGoal;
+use tetris_core::{Game, GameOptions, Mode, RandomizerKind};
+
+const MAGIC: &[u8; 4] = b"TRPL";
+/// Versions before 4 spawned pieces in other places or orientations, so their inputs no longer
+/// play back the same game. Version 4 predates game modes and plays back as a marathon, and
+/// version 5 predates marathon goals and starting levels, playing back as an endless marathon
+/// from level 0. Versions before 7 play back with the default lock delay.
+const VERSION: u8 = 7;
+const MARATHON_VERSION: u8 = 4;
+const ENDLESS_MARATHON_VERSION: u8 = 5;
+const LOCK_DELAY_VERSION: u8 = 7;
+pub const SPEEDS: [u64; 4] = [1, 2, 4, 8];
+
+/// A recorded message that changes the game. Frames are stored as the milliseconds they let
+/// pass, so playback does not depend on how fast it is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Tick,
+    Advance(u64),
+    Move(Direction),
+    RotateClockwise,
+    Rotate,
+    Hold,
+    TogglePause,
+}
+
+impl Event {
+    pub fn from_message(message: Message) -> Option<Self> {
+        match message {
+            Message::Tick(_) => Some(Event::Tick),
+            Message::Advance(elapsed_ms) => Some(Event::Advance(elapsed_ms)),
+            Message::Move(dir) => Some(Event::Move(dir)),
+            Message::RotateClockwise => Some(Event::RotateClockwise),
+            Message::Rotate => Some(Event::Rotate),
+            Message::Hold => Some(Event::Hold),
+            Message::TogglePause => Some(Event::TogglePause),
+            _ => None,
+        }
+    }
+
+    pub fn to_message(self) -> Message {
+        match self {
+            Event::Tick => Message::Tick(chrono::offset::Local::now()),
+            Event::Advance(elapsed_ms) => Message::Advance(elapsed_ms),
+            Event::Move(dir) => Message::Move(dir),
+            Event::RotateClockwise => Message::RotateClockwise,
+            Event::Rotate => Message::Rotate,
+            Event::Hold => Message::Hold,
+            Event::TogglePause => Message::TogglePause,
+        }
+    }
+
+    fn opcode(self) -> u8 {
+        match self {
+            Event::Tick => 0,
+            Event::Advance(_) => 1,
+            Event::Move(Direction::Left) => 2,
+            Event::Move(Direction::Right) => 3,
+            Event::Move(Direction::Bottom) => 4,
+            Event::Move(Direction::HardDrop) => 5,
+            Event::RotateClockwise => 6,
+            Event::Rotate => 7,
+            Event::Hold => 8,
+            Event::TogglePause => 9,
+        }
+    }
+}
+
+/// One game: the seed and options it started with and every event with its time in
+/// milliseconds since the start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    pub seed: u64,
+    pub options: GameOptions,
+    pub lock_delay_ms: u64,
+    pub events: Vec<(u64, Event)>,
+}
+
+impl Replay {
+    /// An empty replay of `game`, which has to be freshly started.
+    pub fn new(game: &Game) -> Self {
+        Self {
+            seed: game.seed(),
+            options: game.options(),
+            lock_delay_ms: game.lock_delay.delay_ms,
+            events: Vec::new(),
+        }
+    }
+
+    /// Binary layout: magic, version, seed (u64 LE), randomizer index, mode index, the goal
+    /// index and starting level of a marathon, the minutes of an ultra or the garbage rows and
+    /// messiness of a dig, the messiness of a survival or whether a zen game has gravity, board
+    /// width and height, lock delay, event count, then per event the time since the previous one, an opcode
+    /// and the elapsed time of frames. All sizes, counts and times are LEB128 varints.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+
+        let randomizer = RandomizerKind::ALL
+            .iter()
+            .position(|&kind| kind == self.options.randomizer)
+            .unwrap();
+        bytes.push(randomizer as u8);
+
+        let mode = Mode::ALL.iter().position(|&mode| mode == self.options.mode).unwrap();
+        bytes.push(mode as u8);
+
+        match self.options.mode {
+            Mode::Marathon => {
+                let goal = MarathonGoal::ALL
+                    .iter()
+                    .position(|&goal| goal == self.options.marathon_goal)
+                    .unwrap();
+                bytes.push(goal as u8);
+                bytes.push(self.options.start_level as u8);
+            }
+            Mode::Ultra => bytes.push(self.options.ultra_minutes as u8),
+            Mode::Dig => {
+                bytes.push(self.options.dig_rows as u8);
+                bytes.push(self.options.messiness as u8);
+            }
+            Mode::Survival => bytes.push(self.options.messiness as u8),
+            Mode::Zen => bytes.push(self.options.gravity as u8),
+            Mode::Sprint | Mode::Master | Mode::Puzzle => {}
+        }
+
+        write_varint(&mut bytes, self.options.width as u64);
+        write_varint(&mut bytes, self.options.height as u64);
+        write_varint(&mut bytes, self.lock_delay_ms);
+        write_varint(&mut bytes, self.events.len() as u64);
+
+        let mut last_time = 0;
+
+        for &(time_ms, event) in &self.events {
+            write_varint(&mut bytes, time_ms - last_time);
+            bytes.push(event.opcode());
+
+            if let Event::Advance(elapsed_ms) = event {
+                write_varint(&mut bytes, elapsed_ms);
+            }
+
+            last_time = time_ms;
+        }
+
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = Reader { bytes, position: 0 };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(invalid_data("not a replay file"));
+        }
+
+        let version = reader.byte()?;
+        if version < MARATHON_VERSION {
+            return Err(invalid_data(&format!("replay version {} was recorded by an older version", version)));
+        }
+
+        if version > VERSION {
+            return Err(invalid_data(&format!("unsupported replay version {}", version)));
+        }
+
+        let seed = u64::from_le_bytes(reader.take(8)?.try_into().unwrap());
+        let randomizer = *RandomizerKind::ALL
+            .get(reader.byte()? as usize)
+            .ok_or_else(|| invalid_data("unknown randomizer"))?;
+
+        let mode = match version {
+            MARATHON_VERSION => Mode::Marathon,
+            _ => *Mode::ALL
+                .get(reader.byte()? as usize)
+                .ok_or_else(|| invalid_data("unknown mode"))?,
+        };
+
+        let mut options = GameOptions {
+            randomizer,
+            mode,
+            ..GameOptions::default()
+        };
+
+        match mode {
+            Mode::Marathon if version > ENDLESS_MARATHON_VERSION => {
+                options.marathon_goal = *MarathonGoal::ALL
+                    .get(reader.byte()? as usize)
+                    .ok_or_else(|| invalid_data("unknown marathon goal"))?;
+                options.start_level = reader.byte()? as u32;
+
+                if options.start_level > MAX_START_LEVEL {
+                    return Err(invalid_data(&format!("unsupported start level {}", options.start_level)));
+                }
+            }
+            Mode::Ultra => {
+                options.ultra_minutes = reader.byte()? as u32;
+
+                if !(MIN_ULTRA_MINUTES..=MAX_ULTRA_MINUTES).contains(&options.ultra_minutes) {
+                    return Err(invalid_data(&format!("unsupported ultra length {}", options.ultra_minutes)));
+                }
+            }
+            Mode::Dig => {
+                options.dig_rows = reader.byte()? as usize;
+                options.messiness = reader.byte()? as u32;
+
+                if !(1..=MAX_DIG_ROWS).contains(&options.dig_rows) || options.messiness > MAX_MESSINESS {
+                    return Err(invalid_data(&format!(
+                        "unsupported dig of {} rows at {}% messiness",
+                        options.dig_rows, options.messiness
+                    )));
+                }
+            }
+            Mode::Survival => {
+                options.messiness = reader.byte()? as u32;
+
+                if options.messiness > MAX_MESSINESS {
+                    return Err(invalid_data(&format!("unsupported messiness {}", options.messiness)));
+                }
+            }
+            Mode::Zen => {
+                options.gravity = match reader.byte()? {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(invalid_data("invalid zen gravity")),
+                };
+            }
+            Mode::Puzzle => return Err(invalid_data("puzzles are not recorded")),
+            _ => {}
+        }
+
+        options.width = reader.varint()? as usize;
+        options.height = reader.varint()? as usize;
+
+        if !(MIN_BOARD_WIDTH..=MAX_BOARD_WIDTH).contains(&options.width)
+            || !(MIN_BOARD_HEIGHT..=MAX_BOARD_HEIGHT).contains(&options.height)
+        {
+            return Err(invalid_data(&format!(
+                "unsupported board size {}x{}",
+                options.width, options.height
+            )));
+        }
+
+        let lock_delay_ms = match version {
+            LOCK_DELAY_VERSION.. => reader.varint()?,
+            _ => DEFAULT_LOCK_DELAY_MS,
+        };
+
+        let count = reader.varint()?;
+        let mut events = Vec::new();
+        let mut time_ms: u64 = 0;
+
+        for _ in 0..count {
+            time_ms = time_ms
+                .checked_add(reader.varint()?)
+                .ok_or_else(|| invalid_data("event time overflows"))?;
+
+            let event = match reader.byte()? {
+                0 => Event::Tick,
+                1 => Event::Advance(reader.varint()?),
+                2 => Event::Move(Direction::Left),
+                3 => Event::Move(Direction::Right),
+                4 => Event::Move(Direction::Bottom),
+                5 => Event::Move(Direction::HardDrop),
+                6 => Event::RotateClockwise,
+                7 => Event::Rotate,
+                8 => Event::Hold,
+                9 => Event::TogglePause,
+                opcode => return Err(invalid_data(&format!("unknown event {}", opcode))),
+            };
+
+            events.push((time_ms, event));
+        }
+
+        if reader.position != bytes.len() {
+            return Err(invalid_data("trailing data after the last event"));
+        }
+
+        Ok(Self {
+            seed,
+            options,
+            lock_delay_ms,
+            events,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.encode())
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::decode(&std::fs::read(path)?)
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+
+    bytes.push(value as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> io::Result<&'a [u8]> {
+        let end = self.position + count;
+        let slice = self
+            .bytes
+            .get(self.position..end)
+            .ok_or_else(|| invalid_data("replay file is truncated"))?;
+
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> io::Result<u64> {
+        let mut value = 0;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(invalid_data("varint is too long"))
+    }
+}
+
+/// Records the events of the running game.
+#[derive(Debug)]
+pub struct Recorder {
+    replay: Replay,
+    started: Instant,
+}
+
+impl Recorder {
+    pub fn new(game: &Game) -> Self {
+        Self {
+            replay: Replay::new(game),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn record(&mut self, event: Event) {
+        let time_ms = self.started.elapsed().as_millis() as u64;
+        self.replay.events.push((time_ms, event));
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+}
+
+/// Plays a replay back on its own clock, which can be paused, sped up or stepped frame by frame.
+#[derive(Debug)]
+pub struct Playback {
+    pub replay: Replay,
+    pub paused: bool,
+    speed_index: usize,
+    position: usize,
+    clock_ms: u64,
+}
+
+impl Playback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            paused: false,
+            speed_index: 0,
+            position: 0,
+            clock_ms: 0,
+        }
+    }
+
+    pub fn speed(&self) -> u64 {
+        SPEEDS[self.speed_index]
+    }
+
+    pub fn faster(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.replay.events.len()
+    }
+
+    /// Lets `elapsed_ms` of real time pass and returns the events that became due.
+    pub fn advance(&mut self, elapsed_ms: u64) -> Vec<Event> {
+        if self.paused {
+            return Vec::new();
+        }
+
+        self.clock_ms = self.clock_ms.saturating_add(elapsed_ms.saturating_mul(self.speed()));
+
+        let due = self.replay.events[self.position..]
+            .iter()
+            .take_while(|(time_ms, _)| *time_ms <= self.clock_ms)
+            .count();
+
+        self.take(due)
+    }
+
+    /// Returns every event up to and including the next recorded frame.
+    pub fn step_frame(&mut self) -> Vec<Event> {
+        let count = self.replay.events[self.position..]
+            .iter()
+            .position(|(_, event)| matches!(event, Event::Advance(_)))
+            .map_or(self.replay.events.len() - self.position, |index| index + 1);
+
+        let events = self.take(count);
+
+        if let Some(&(time_ms, _)) = self.replay.events[..self.position].last() {
+            self.clock_ms = self.clock_ms.max(time_ms);
+        }
+
+        events
+    }
+
+    fn take(&mut self, count: usize) -> Vec<Event> {
+        let end = self.position + count;
+        let events = self.replay.events[self.position..end]
+            .iter()
+            .map(|&(_, event)| event)
+            .collect();
+
+        self.position = end;
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::State;
+    use crate::update::update;
+
+    fn sample_replay() -> Replay {
+        Replay {
+            seed: 42,
+            options: GameOptions {
+                width: 12,
+                height: 30,
+                randomizer: RandomizerKind::Tgm2,
+                mode: Mode::Sprint,
+                ..GameOptions::default()
+            },
+            lock_delay_ms: 300,
+            events: vec![
+                (0, Event::TogglePause),
+                (16, Event::Advance(16)),
+                (20, Event::Move(Direction::Left)),
+                (32, Event::Advance(16)),
+                (300, Event::RotateClockwise),
+                (800, Event::Tick),
+                (800, Event::Advance(468)),
+                (900, Event::Move(Direction::HardDrop)),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let replay = sample_replay();
+        let bytes = replay.encode();
+
+        assert_eq!(Replay::decode(&bytes).unwrap(), replay);
+        assert!(bytes.len() < 50);
+    }
+
+    #[test]
+    fn test_decode_rejects_corrupt_files() {
+        let bytes = sample_replay().encode();
+
+        assert!(Replay::decode(b"nope").is_err());
+        assert!(Replay::decode(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut version = bytes.clone();
+        version[4] = 99;
+        assert!(Replay::decode(&version).is_err());
+
+        let mut mode = bytes.clone();
+        mode[14] = 200;
+        assert!(Replay::decode(&mode).is_err());
+
+        let mut puzzle = bytes.clone();
+        puzzle[14] = Mode::ALL.iter().position(|&mode| mode == Mode::Puzzle).unwrap() as u8;
+        assert!(Replay::decode(&puzzle).is_err());
+
+        let mut size = bytes.clone();
+        size[15] = 2;
+        assert!(Replay::decode(&size).is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(Replay::decode(&trailing).is_err());
+
+        let mut opcode = bytes.clone();
+        let last = opcode.len() - 1;
+        opcode[last] = 200;
+        assert!(Replay::decode(&opcode).is_err());
+    }
+
+    #[test]
+    fn test_ultra_length_round_trip() {
+        let mut replay = sample_replay();
+        replay.options.mode = Mode::Ultra;
+        replay.options.ultra_minutes = 2;
+
+        let bytes = replay.encode();
+        assert_eq!(Replay::decode(&bytes).unwrap(), replay);
+
+        let mut length = bytes.clone();
+        length[15] = 5;
+        assert!(Replay::decode(&length).is_err());
+    }
+
+    #[test]
+    fn test_dig_settings_round_trip() {
+        let mut replay = sample_replay();
+        replay.options.mode = Mode::Dig;
+        replay.options.dig_rows = 8;
+        replay.options.messiness = 40;
+
+        let bytes = replay.encode();
+        assert_eq!(Replay::decode(&bytes).unwrap(), replay);
+
+        let mut messiness = bytes.clone();
+        messiness[16] = 101;
+        assert!(Replay::decode(&messiness).is_err());
+    }
+
+    #[test]
+    fn test_zen_gravity_round_trip() {
+        let mut replay = sample_replay();
+        replay.options.mode = Mode::Zen;
+        replay.options.gravity = false;
+
+        let bytes = replay.encode();
+        assert_eq!(Replay::decode(&bytes).unwrap(), replay);
+
+        let mut gravity = bytes.clone();
+        gravity[15] = 2;
+        assert!(Replay::decode(&gravity).is_err());
+    }
+
+    #[test]
+    fn test_marathon_settings_round_trip() {
+        let mut replay = sample_replay();
+        replay.options.mode = Mode::Marathon;
+        replay.options.marathon_goal = MarathonGoal::Lines200;
+        replay.options.start_level = 15;
+
+        let bytes = replay.encode();
+        assert_eq!(Replay::decode(&bytes).unwrap(), replay);
+
+        let mut level = bytes.clone();
+        level[16] = 30;
+        assert!(Replay::decode(&level).is_err());
+
+        // Version 5 had no marathon settings.
+        let mut old = bytes.clone();
+        old[4] = 5;
+        old.drain(15..17);
+        old.drain(17..19);
+        let decoded = Replay::decode(&old).unwrap();
+        assert_eq!(decoded.options.marathon_goal, MarathonGoal::Endless);
+        assert_eq!(decoded.options.start_level, 0);
+        assert_eq!(decoded.lock_delay_ms, DEFAULT_LOCK_DELAY_MS);
+    }
+
+    #[test]
+    fn test_version_four_is_a_marathon() {
+        let mut bytes = sample_replay().encode();
+        bytes[4] = 4;
+        bytes.remove(14);
+        bytes.drain(16..18);
+
+        let replay = Replay::decode(&bytes).unwrap();
+        assert_eq!(replay.options.mode, Mode::Marathon);
+        assert_eq!(replay.events, sample_replay().events);
+    }
+
+    #[test]
+    fn test_older_versions_are_rejected() {
+        let mut bytes = sample_replay().encode();
+        bytes[4] = 2;
+
+        let error = Replay::decode(&bytes).unwrap_err();
+        assert!(error.to_string().contains("older version"), "{}", error);
+    }
+
+    #[test]
+    fn test_playback_follows_clock_and_speed() {
+        let mut playback = Playback::new(sample_replay());
+
+        assert_eq!(playback.advance(10), vec![Event::TogglePause]);
+        assert_eq!(playback.advance(10), vec![Event::Advance(16), Event::Move(Direction::Left)]);
+
+        playback.paused = true;
+        assert!(playback.advance(1000).is_empty());
+
+        playback.paused = false;
+        playback.faster();
+        assert_eq!(playback.speed(), 2);
+        assert_eq!(playback.advance(140), vec![Event::Advance(16), Event::RotateClockwise]);
+    }
+
+    #[test]
+    fn test_step_frame_stops_after_next_frame() {
+        let mut playback = Playback::new(sample_replay());
+        playback.paused = true;
+
+        assert_eq!(playback.step_frame(), vec![Event::TogglePause, Event::Advance(16)]);
+        assert_eq!(playback.step_frame(), vec![Event::Move(Direction::Left), Event::Advance(16)]);
+        assert_eq!(
+            playback.step_frame(),
+            vec![Event::RotateClockwise, Event::Tick, Event::Advance(468)]
+        );
+        assert_eq!(playback.step_frame(), vec![Event::Move(Direction::HardDrop)]);
+        assert!(playback.is_finished());
+        assert!(playback.step_frame().is_empty());
+    }
+
+    #[test]
+    fn test_playback_reproduces_the_game() {
+        let messages = [
+            Message::TogglePause,
+            Message::Move(Direction::Left),
+            Message::Advance(16),
+            Message::RotateClockwise,
+            Message::Hold,
+            Message::Move(Direction::HardDrop),
+            Message::Move(Direction::Right),
+            Message::Move(Direction::Right),
+            Message::Tick(chrono::offset::Local::now()),
+            Message::Rotate,
+            Message::Move(Direction::Bottom),
+            Message::Advance(600),
+            Message::Move(Direction::HardDrop),
+        ];
+
+        let mut state = State {
+            seed: Some(11),
+            lock_delay_ms: 200,
+            ..State::default()
+        };
+        let _ = update(&mut state, Message::Initialize);
+
+        for message in messages {
+            let _ = update(&mut state, message);
+        }
+
+        let replay = state.recorder.as_ref().unwrap().replay().clone();
+        let replay = Replay::decode(&replay.encode()).unwrap();
+        assert_eq!(replay.seed, 11);
+        assert_eq!(replay.lock_delay_ms, 200);
+
+        let mut replayed = State {
+            playback: Some(Playback::new(replay)),
+            ..State::default()
+        };
+        let _ = update(&mut replayed, Message::Initialize);
+
+        let mut playback = replayed.playback.take().unwrap();
+        for event in playback.advance(u64::MAX / 8) {
+            let _ = update(&mut replayed, event.to_message());
+        }
+
+        assert_eq!(replayed.game.game_space, state.game.game_space);
+        assert_eq!(replayed.game.score, state.game.score);
+        assert_eq!(replayed.game.hold_item, state.game.hold_item);
+        assert_eq!(replayed.game.active_piece, state.game.active_piece);
+    }
+
+    #[test]
+    fn test_playback_pauses_where_the_game_was_saved() {
+        let messages = [
+            Message::TogglePause,
+            Message::Move(Direction::Left),
+            Message::Advance(16),
+            Message::SaveGame,
+            Message::TogglePause,
+            Message::Move(Direction::Right),
+            Message::RotateClockwise,
+            Message::Move(Direction::HardDrop),
+            Message::Advance(600),
+            Message::Move(Direction::HardDrop),
+        ];
+
+        let mut state = State {
+            seed: Some(7),
+            ..State::default()
+        };
+        let _ = update(&mut state, Message::Initialize);
+
+        for message in messages {
+            let _ = update(&mut state, message);
+        }
+        assert!(state.is_running);
+
+        let replay = state.recorder.as_ref().unwrap().replay().clone();
+        let replay = Replay::decode(&replay.encode()).unwrap();
+
+        let mut replayed = State {
+            playback: Some(Playback::new(replay)),
+            ..State::default()
+        };
+        let _ = update(&mut replayed, Message::Initialize);
+
+        let mut playback = replayed.playback.take().unwrap();
+        for event in playback.advance(u64::MAX / 8) {
+            let _ = update(&mut replayed, event.to_message());
+        }
+
+        assert!(replayed.is_running);
+        assert_eq!(replayed.game.game_space, state.game.game_space);
+        assert_eq!(replayed.game.score, state.game.score);
+        assert_eq!(replayed.game.active_piece, state.game.active_piece);
+    }
+}
@@ -0,0 +1,87 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use tetris_core::Game;
+
+/// Writes the game so the next launch can resume it.
+pub fn save(game: &Game, path: &Path) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    std::fs::write(path, game.to_save())
+}
+
+/// Reads a saved game, returning `None` when there is none.
+pub fn load(path: &Path) -> io::Result<Option<Game>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+
+    Game::from_save(&text)
+        .map(Some)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/// Removes a saved game that has been resumed or has ended.
+pub fn discard(path: &Path) {
+    match std::fs::remove_file(path) {
+        Ok(()) => {}
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+        Err(error) => eprintln!("Could not remove the saved game {}: {}", path.display(), error),
+    }
+}
+
+/// Moves an unreadable save out of the way so it is not tried again, keeping it for inspection.
+pub fn set_aside(path: &Path) -> PathBuf {
+    let mut corrupt = path.as_os_str().to_owned();
+    corrupt.push(".corrupt");
+    let corrupt = PathBuf::from(corrupt);
+
+    if let Err(error) = std::fs::rename(path, &corrupt) {
+        eprintln!("Could not move the saved game {}: {}", path.display(), error);
+    }
+
+    corrupt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("tetris-saved-game-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_save_load_discard() {
+        let path = temp_path("save.txt");
+        let game = Game::with_seed(11);
+
+        save(&game, &path).unwrap();
+        let loaded = load(&path).unwrap().unwrap();
+        assert_eq!(loaded.to_save(), game.to_save());
+
+        discard(&path);
+        assert!(load(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_corrupt_save_is_set_aside() {
+        let path = temp_path("corrupt.txt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "tetris-save 1\nrandomizer ???\n").unwrap();
+
+        let error = load(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let corrupt = set_aside(&path);
+        assert!(!path.exists());
+        assert!(corrupt.exists());
+
+        std::fs::remove_file(corrupt).unwrap();
+    }
+}
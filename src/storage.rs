@@ -0,0 +1,67 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use tetris_core::constants::{BOARD_HEIGHT, BOARD_WIDTH};
+
+const APP_DIR: &str = "tetris";
+
+/// `$XDG_DATA_HOME/tetris`, falling back to `~/.local/share/tetris`.
+pub fn data_dir() -> Option<PathBuf> {
+    data_dir_from(std::env::var_os("XDG_DATA_HOME"), std::env::var_os("HOME"))
+}
+
+fn data_dir_from(xdg_data_home: Option<OsString>, home: Option<OsString>) -> Option<PathBuf> {
+    // The spec says relative paths in XDG variables are invalid and must be ignored.
+    let base = xdg_data_home
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| home.map(|home| PathBuf::from(home).join(".local").join("share")))?;
+
+    Some(base.join(APP_DIR))
+}
+
+/// Path of `file_name` inside the data directory.
+pub fn data_file(file_name: &str) -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(file_name))
+}
+
+/// `file_name` for the standard 10x20 board, with the board size added before the extension
+/// for any other, as in `high_scores_12x30.txt`, so records of different boards are kept apart.
+pub fn sized_file_name(file_name: &str, width: usize, height: usize) -> String {
+    if (width, height) == (BOARD_WIDTH, BOARD_HEIGHT) {
+        return file_name.to_string();
+    }
+
+    match file_name.rsplit_once('.') {
+        Some((stem, extension)) => format!("{}_{}x{}.{}", stem, width, height, extension),
+        None => format!("{}_{}x{}", file_name, width, height),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xdg_data_home_wins() {
+        let dir = data_dir_from(Some("/data".into()), Some("/home/me".into()));
+        assert_eq!(dir, Some(PathBuf::from("/data/tetris")));
+    }
+
+    #[test]
+    fn test_falls_back_to_home() {
+        let dir = data_dir_from(None, Some("/home/me".into()));
+        assert_eq!(dir, Some(PathBuf::from("/home/me/.local/share/tetris")));
+
+        let dir = data_dir_from(Some("relative".into()), Some("/home/me".into()));
+        assert_eq!(dir, Some(PathBuf::from("/home/me/.local/share/tetris")));
+
+        assert_eq!(data_dir_from(None, None), None);
+    }
+
+    #[test]
+    fn test_sized_file_name() {
+        assert_eq!(sized_file_name("sprint_pb.txt", BOARD_WIDTH, BOARD_HEIGHT), "sprint_pb.txt");
+        assert_eq!(sized_file_name("sprint_pb.txt", 4, 20), "sprint_pb_4x20.txt");
+        assert_eq!(sized_file_name("scores", 10, 40), "scores_10x40");
+    }
+}
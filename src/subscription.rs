@@ -2,25 +2,104 @@ use std::time::Duration;
 use iced::{keyboard, Subscription};
 use iced::keyboard::Key::Named;
 use crate::canvas::State;
+use crate::constants::FRAME_RATE_MS;
 use crate::enums::Direction;
 use crate::update::Message;
 
+fn movement_key(key: keyboard::Key) -> Option<Direction> {
+    match key.as_ref() {
+        Named(keyboard::key::Named::ArrowLeft) => Some(Direction::Left),
+        Named(keyboard::key::Named::ArrowRight) => Some(Direction::Right),
+        Named(keyboard::key::Named::ArrowDown) => Some(Direction::Bottom),
+        Named(keyboard::key::Named::ArrowUp) => Some(Direction::HardDrop),
+        _ => None,
+    }
+}
+
 pub fn subscription(state: &State) -> Subscription<Message> {
     fn handle_hotkey(key: keyboard::Key, _modifiers: keyboard::Modifiers) -> Option<Message> {
         match key.as_ref() {
             keyboard::Key::Character("q") => Some(Message::RotateClockwise),
             keyboard::Key::Character("w") => Some(Message::Rotate),
+            keyboard::Key::Character("c") => Some(Message::Hold),
+            keyboard::Key::Character("s") => Some(Message::SaveGame),
+            Named(keyboard::key::Named::Shift) => Some(Message::Hold),
             Named(keyboard::key::Named::Space) => Some(Message::TogglePause),
-            Named(keyboard::key::Named::ArrowLeft) => Some(Message::Move(Direction::Left)),
-            Named(keyboard::key::Named::ArrowRight) => Some(Message::Move(Direction::Right)),
-            Named(keyboard::key::Named::ArrowDown) => Some(Message::Move(Direction::Bottom)),
+            _ => movement_key(key).map(Message::Press),
+        }
+    }
+
+    fn handle_playback_hotkey(key: keyboard::Key, _modifiers: keyboard::Modifiers) -> Option<Message> {
+        match key.as_ref() {
+            Named(keyboard::key::Named::Space) => Some(Message::TogglePlayback),
+            Named(keyboard::key::Named::ArrowRight) => Some(Message::PlaybackStep),
+            Named(keyboard::key::Named::ArrowUp) => Some(Message::PlaybackFaster),
+            Named(keyboard::key::Named::ArrowDown) => Some(Message::PlaybackSlower),
+            _ => None,
+        }
+    }
+
+    fn handle_name_key(key: keyboard::Key, _modifiers: keyboard::Modifiers) -> Option<Message> {
+        match key.as_ref() {
+            Named(keyboard::key::Named::Enter) => Some(Message::NameSubmit),
+            Named(keyboard::key::Named::Backspace) => Some(Message::NameBackspace),
+            Named(keyboard::key::Named::Space) => Some(Message::NameCharacter(' ')),
+            keyboard::Key::Character(text) => text.chars().next().map(Message::NameCharacter),
+            _ => None,
+        }
+    }
+
+    fn handle_puzzle_menu_key(key: keyboard::Key, _modifiers: keyboard::Modifiers) -> Option<Message> {
+        match key.as_ref() {
+            Named(keyboard::key::Named::ArrowUp) => Some(Message::PuzzlePrevious),
+            Named(keyboard::key::Named::ArrowDown) => Some(Message::PuzzleNext),
+            Named(keyboard::key::Named::Enter | keyboard::key::Named::Space) => Some(Message::PuzzleStart),
             _ => None,
         }
     }
 
+    fn handle_release(key: keyboard::Key, _modifiers: keyboard::Modifiers) -> Option<Message> {
+        movement_key(key).map(Message::Release)
+    }
+
+    let frames = if state.is_running || state.playback.is_some() {
+        iced::time::every(Duration::from_millis(FRAME_RATE_MS)).map(Message::Frame)
+    } else {
+        Subscription::none()
+    };
+
+    let close_requests = iced::window::close_requests().map(Message::CloseRequested);
+
+    if state.name_entry.is_some() {
+        return Subscription::batch([close_requests, keyboard::on_key_press(handle_name_key)]);
+    }
+
+    if state.puzzle_menu {
+        return Subscription::batch([close_requests, keyboard::on_key_press(handle_puzzle_menu_key)]);
+    }
+
+    // Gravity and keys come from the recording while a replay plays.
+    if state.playback.is_some() {
+        return Subscription::batch([
+            close_requests,
+            frames,
+            keyboard::on_key_press(handle_playback_hotkey),
+        ]);
+    }
+
+    // Gravity counted in G is applied by the frames instead of by ticks, and zen can do without.
+    let ticks = if state.game.mode().has_frame_gravity() || !state.game.options().has_gravity() {
+        Subscription::none()
+    } else {
+        iced::time::every(Duration::from_millis(state.game.tick_rate_ms()))
+            .map(|_| Message::Tick(chrono::offset::Local::now()))
+    };
+
     Subscription::batch([
-        iced::time::every(Duration::from_millis(state.tick_rate_ms))
-            .map(|_| Message::Tick(chrono::offset::Local::now())),
+        close_requests,
+        ticks,
+        frames,
         keyboard::on_key_press(handle_hotkey),
+        keyboard::on_key_release(handle_release),
     ])
 }
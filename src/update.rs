@@ -1,132 +1,524 @@
-use crate::bag::Bag;
 use crate::canvas::State;
-use crate::constants::{NUM_OF_SQUARES_X, NUM_OF_SQUARES_Y};
 use crate::enums::Direction;
-use crate::level::{get_level, get_speed_by_level};
-use crate::moves::{draw_game_over_brick, is_game_over, move_bottom, move_left, move_right};
-use crate::playground::Playground;
-use crate::rotations::{rotate_clockwise, rotate_counterclockwise};
-use crate::types::{Matrix, TimeLocal};
-use iced::Task;
+use crate::high_scores::{HighScore, HighScores, MAX_NAME_LENGTH, clean_name, table_file};
+use crate::personal_best::{SprintRecord, record_file};
+use crate::replay::{Event, Recorder};
+use crate::saved_game;
+use crate::types::TimeLocal;
+use iced::{Task, window};
+use iced::time::Instant;
+use tetris_core::randomizer::random_seed;
+use tetris_core::{Game, Input, Mode};
 
 #[derive(Debug, Clone, Copy)]
 pub enum Message {
     Initialize,
     Tick(TimeLocal),
+    Frame(Instant),
+    Advance(u64),
     Move(Direction),
+    Press(Direction),
+    Release(Direction),
     RotateClockwise,
     Rotate,
+    Hold,
     TogglePause,
+    TogglePlayback,
+    PlaybackFaster,
+    PlaybackSlower,
+    PlaybackStep,
+    NameCharacter(char),
+    NameBackspace,
+    NameSubmit,
+    SaveGame,
+    PuzzlePrevious,
+    PuzzleNext,
+    PuzzleStart,
+    CloseRequested(window::Id),
+}
+
+fn step(state: &mut State, input: Input) {
+    if !state.is_running {
+        return;
+    }
+
+    state.game.step(input);
+    check_game_over(state);
+
+    state.playground.clear();
+}
+
+/// Stops a game that just ended and asks for a name when its score made the high score table.
+/// A finished sprint is kept instead when it beats the personal best; digs and puzzles keep no
+/// records, and zen games never end: topping out clears their board.
+fn check_game_over(state: &mut State) {
+    if !state.game.game_over || !state.is_running {
+        return;
+    }
+
+    state.is_running = false;
+    state.new_rank = None;
+    state.best_split_ms = None;
+    state.new_personal_best = false;
+
+    // A puzzle leaves the saved game alone, as it was never resumed.
+    if state.playback.is_none()
+        && state.game.puzzle.is_none()
+        && let Some(path) = &state.save_path
+    {
+        saved_game::discard(path);
+    }
+
+    if state.playback.is_some() {
+        return;
+    }
+
+    match state.game.mode() {
+        Mode::Marathon | Mode::Ultra | Mode::Survival | Mode::Master => {
+            if state.high_scores.qualifies(state.game.score) {
+                state.name_entry = Some(String::new());
+            }
+        }
+        Mode::Sprint => {
+            if state.game.completed {
+                save_personal_best(state);
+            }
+        }
+        Mode::Dig | Mode::Zen | Mode::Puzzle => {}
+    }
+}
+
+/// Keeps the sprint that just finished when it is faster than the personal best.
+fn save_personal_best(state: &mut State) {
+    let record = SprintRecord::from_game(&state.game);
+
+    if let Some(best) = &state.personal_best {
+        state.best_split_ms = Some(record.time_ms as i64 - best.time_ms as i64);
+
+        if best.time_ms <= record.time_ms {
+            return;
+        }
+    }
+
+    if let Some(path) = &state.personal_best_path
+        && let Err(error) = record.save(path)
+    {
+        eprintln!("Could not save the sprint record to {}: {}", path.display(), error);
+    }
+
+    state.personal_best = Some(record);
+    state.new_personal_best = true;
+}
+
+/// Starts recording the current game, unless a replay is being played. Puzzles are not
+/// recorded, as a replay cannot hold their board.
+fn start_recording(state: &mut State) {
+    if state.playback.is_none() && state.game.puzzle.is_none() {
+        state.recorder = Some(Recorder::new(&state.game));
+    }
+}
+
+/// Records `message` if it changed the game, and writes the replay out once the game is over.
+fn record(state: &mut State, message: Message) {
+    let Some(recorder) = state.recorder.as_mut() else {
+        return;
+    };
+
+    if let Some(event) = Event::from_message(message) {
+        recorder.record(event);
+    }
+
+    if !state.game.game_over {
+        return;
+    }
+
+    let (Some(recorder), Some(path)) = (state.recorder.take(), state.record_path.as_ref()) else {
+        return;
+    };
+
+    if let Err(error) = recorder.replay().save(path) {
+        eprintln!("Could not save the replay to {}: {}", path.display(), error);
+    }
+}
+
+/// Saves a game that is still being played, so it can be resumed on the next launch.
+fn save_game(state: &State) {
+    if state.playback.is_some() || state.game.game_over || state.game.puzzle.is_some() {
+        return;
+    }
+
+    if let Some(path) = &state.save_path
+        && let Err(error) = saved_game::save(&state.game, path)
+    {
+        eprintln!("Could not save the game to {}: {}", path.display(), error);
+    }
+}
+
+/// Takes the game saved by the previous launch, if there is one. A save that cannot be read is
+/// reported and moved aside so a new game starts instead.
+fn resume_game(state: &State) -> Option<Game> {
+    let path = state.save_path.as_ref()?;
+
+    match saved_game::load(path) {
+        Ok(game) => {
+            saved_game::discard(path);
+            game.filter(|game| !game.game_over)
+        }
+        Err(error) => {
+            let corrupt = saved_game::set_aside(path);
+            eprintln!(
+                "Could not resume the saved game {}: {} (moved to {})",
+                path.display(),
+                error,
+                corrupt.display()
+            );
+            None
+        }
+    }
+}
+
+/// Switches to the high score table of `game`'s category, as a resumed game may have been
+/// started with other options than this launch.
+fn use_high_scores_of(state: &mut State, game: &Game) {
+    let file = table_file(&game.options());
+
+    let Some(path) = state.high_scores_path.as_ref().map(|path| path.with_file_name(&file)) else {
+        return;
+    };
+
+    if Some(&path) != state.high_scores_path.as_ref() {
+        state.high_scores = HighScores::load_or_default(&path);
+        state.high_scores_path = Some(path);
+    }
+}
+
+/// Switches to the sprint record of `game`'s board size, for the same reason.
+fn use_personal_best_of(state: &mut State, game: &Game) {
+    let file = record_file(&game.options());
+
+    let Some(path) = state.personal_best_path.as_ref().map(|path| path.with_file_name(&file)) else {
+        return;
+    };
+
+    if Some(&path) != state.personal_best_path.as_ref() {
+        state.personal_best = SprintRecord::load(&path).unwrap_or_else(|error| {
+            eprintln!("Could not load the sprint record from {}: {}", path.display(), error);
+            None
+        });
+        state.personal_best_path = Some(path);
+    }
+}
+
+/// Gives `game` a lock delay of `delay_ms`, except in master, which times its own by level.
+fn use_lock_delay(game: &mut Game, delay_ms: u64) {
+    if game.mode() != Mode::Master {
+        game.lock_delay.delay_ms = delay_ms;
+    }
+}
+
+/// Sets up the selected puzzle, which shows its board while it is being picked.
+fn show_puzzle(state: &mut State) {
+    state.game = Game::from_puzzle(state.puzzles[state.puzzle_index].clone());
+    use_lock_delay(&mut state.game, state.lock_delay_ms);
+    state.is_running = false;
+    state.last_frame = None;
+    state.playground.clear();
+}
+
+/// Feeds recorded events through `update` exactly like the live messages they stand for.
+fn play(state: &mut State, events: Vec<Event>) {
+    for event in events {
+        let _ = update(state, event.to_message());
+    }
+
+    state.playground.clear();
 }
 
 pub fn update(state: &mut State, message: Message) -> Task<Message> {
+    let task = handle(state, message);
+    record(state, message);
+    task
+}
+
+fn handle(state: &mut State, message: Message) -> Task<Message> {
     match message {
         Message::TogglePause => {
-            if state.game_over {
-                state.game_over = false;
-                state.bag.refill();
-                state.score = 0;
-                state.rows_cleared = 0;
-                state.level = 0;
-                state.tick_rate_ms = get_speed_by_level(state.level);
+            if state.name_entry.is_some() {
+                return Task::none();
+            }
 
-                let (mut item, next_item) = state.bag.get_item();
-                state.next_item = next_item;
+            // Back to the puzzles after one ended, with the next one selected if it was solved.
+            if state.game.game_over && state.game.puzzle.is_some() && state.puzzles.len() > 1 {
+                if state.game.completed {
+                    state.puzzle_index = (state.puzzle_index + 1) % state.puzzles.len();
+                }
+
+                state.puzzle_menu = true;
+                show_puzzle(state);
+                return Task::none();
+            }
 
-                item.set_default_position(&mut state.game_space);
+            if state.game.game_over {
+                let seed = state.seed.unwrap_or_else(random_seed);
+                state.game.restart_with_seed(seed);
+                start_recording(state);
             }
 
             state.is_running = !state.is_running;
+            state.last_frame = None;
             state.playground.clear();
             Task::none()
         }
         Message::Move(dir) => {
-            if !state.is_running {
+            let input = match dir {
+                Direction::Left => Input::Left,
+                Direction::Right => Input::Right,
+                Direction::Bottom => Input::SoftDrop,
+                Direction::HardDrop => Input::HardDrop,
+            };
+
+            step(state, input);
+            Task::none()
+        }
+        Message::Initialize => {
+            // Puzzles are picked from a menu, unless a single one was given on the command line.
+            if state.playback.is_none() && state.game_options.mode == Mode::Puzzle {
+                state.puzzle_menu = state.puzzles.len() > 1;
+                show_puzzle(state);
                 return Task::none();
             }
 
-            if dir == Direction::Right {
-                move_right(&mut state.game_space);
-            } else if dir == Direction::Left {
-                move_left(&mut state.game_space);
-            } else {
-                let moved = move_bottom(&mut state.game_space);
-                
-                if !moved {
-                    let (mut item, next_item) = state.bag.get_item();
-
-                    let (game_over, rows_to_render) = is_game_over(&mut state.game_space, &item);
-                    
-                    if game_over {
-                        println!("Game over!");
-                        println!("Rows to render: {}", rows_to_render);
-
-                        let x_pos = state.game_space[0].len() / 2 - 1;
-                        draw_game_over_brick(&mut state.game_space, x_pos, rows_to_render, &item);
-                        
-                        state.is_running = false;
-                        state.game_over = true;
-                        return Task::none();
-                    } else {
-                        state.next_item = next_item.clone();
-                        item.set_default_position(&mut state.game_space);
-                        
-                        let cleared_rows = Playground::clear_rows(&mut state.game_space, &mut state.score);
-                        state.rows_cleared += cleared_rows;
-                        state.level = get_level(state.rows_cleared);
-                        state.tick_rate_ms = get_speed_by_level(state.level);
-                    }
+            if state.playback.is_none()
+                && let Some(game) = resume_game(state)
+            {
+                // A resumed game starts paused and is not recorded: a replay has to start from
+                // an empty board.
+                use_high_scores_of(state, &game);
+                use_personal_best_of(state, &game);
+                state.game = game;
+                use_lock_delay(&mut state.game, state.lock_delay_ms);
+                state.recorder = None;
+                state.is_running = false;
+                return Task::none();
+            }
+
+            // A replay plays back with the lock delay it was recorded with.
+            let (game, lock_delay_ms) = match &state.playback {
+                Some(playback) => (
+                    Game::with_options(playback.replay.options, playback.replay.seed),
+                    playback.replay.lock_delay_ms,
+                ),
+                None => {
+                    let seed = state.seed.unwrap_or_else(random_seed);
+                    (Game::with_options(state.game_options, seed), state.lock_delay_ms)
                 }
+            };
+            state.game = game;
+            use_lock_delay(&mut state.game, lock_delay_ms);
+
+            start_recording(state);
+            Task::none()
+        }
+        Message::Tick(local_time) => {
+            let now = local_time;
+
+            if now != state.now {
+                state.now = now;
+                state.playground.clear();
             }
 
-            state.playground.clear();
+            step(state, Input::Gravity);
             Task::none()
         }
-        Message::Initialize => {
-            let mut game_space: Matrix = Vec::new();
+        Message::Frame(instant) => {
+            if !state.is_running && state.playback.is_none() {
+                return Task::none();
+            }
 
-            for _ in 0..NUM_OF_SQUARES_Y as usize {
-                game_space.push(vec![None; NUM_OF_SQUARES_X as usize]);
+            let elapsed_ms = state
+                .last_frame
+                .map_or(0, |last_frame| instant.duration_since(last_frame).as_millis() as u64);
+            state.last_frame = Some(instant);
+
+            if let Some(playback) = state.playback.as_mut() {
+                let events = playback.advance(elapsed_ms);
+                play(state, events);
+                return Task::none();
             }
 
-            state.game_space = game_space.clone();
+            let _ = update(state, Message::Advance(elapsed_ms));
 
-            let bag = Bag::new();
-            state.bag = bag;
+            let moves = state
+                .key_repeat
+                .advance(elapsed_ms, &state.handling, state.game.tick_rate_ms(), state.game.width());
 
-            let (mut item, next_item) = state.bag.get_item();
-            state.next_item = next_item;
+            Task::batch(moves.into_iter().map(|dir| Task::done(Message::Move(dir))))
+        }
+        Message::Advance(elapsed_ms) => {
+            if !state.is_running {
+                return Task::none();
+            }
 
-            item.set_default_position(&mut state.game_space);
+            // Clocks are redrawn every frame.
+            if state.game.advance(elapsed_ms) || state.game.mode().is_timed() {
+                state.playground.clear();
+            }
+
+            // The game also ends here when its time runs out.
+            check_game_over(state);
 
             Task::none()
         }
-        Message::Tick(local_time) => {
-            let now = local_time;
+        Message::Press(dir) => {
+            if !state.is_running {
+                return Task::none();
+            }
 
-            if now != state.now {
-                state.now = now;
+            match state.key_repeat.press(dir) {
+                Some(dir) => Task::done(Message::Move(dir)),
+                None => Task::none(),
+            }
+        }
+        Message::Release(dir) => {
+            state.key_repeat.release(dir);
+            Task::none()
+        }
+        Message::RotateClockwise => {
+            step(state, Input::RotateClockwise);
+            Task::none()
+        }
+        Message::Rotate => {
+            step(state, Input::RotateCounterclockwise);
+            Task::none()
+        }
+        Message::Hold => {
+            step(state, Input::Hold);
+            Task::none()
+        }
+        Message::TogglePlayback => {
+            if let Some(playback) = state.playback.as_mut() {
+                playback.paused = !playback.paused;
                 state.playground.clear();
             }
 
-            Task::perform(async {}, |_| Message::Move(Direction::Bottom))
+            Task::none()
         }
-        Message::RotateClockwise => {
-            if state.is_running {
-                rotate_clockwise(&mut state.game_space);
+        Message::PlaybackFaster => {
+            if let Some(playback) = state.playback.as_mut() {
+                playback.faster();
                 state.playground.clear();
             }
 
             Task::none()
         }
+        Message::PlaybackSlower => {
+            if let Some(playback) = state.playback.as_mut() {
+                playback.slower();
+                state.playground.clear();
+            }
 
-        Message::Rotate => {
-            if state.is_running {
-                rotate_counterclockwise(&mut state.game_space);
+            Task::none()
+        }
+        Message::PlaybackStep => {
+            let Some(playback) = state.playback.as_mut() else {
+                return Task::none();
+            };
+
+            if playback.paused {
+                let events = playback.step_frame();
+                play(state, events);
+            }
+
+            Task::none()
+        }
+        Message::NameCharacter(character) => {
+            if let Some(name) = state.name_entry.as_mut()
+                && name.chars().count() < MAX_NAME_LENGTH
+                && !character.is_control()
+            {
+                name.push(character);
+                state.playground.clear();
+            }
+
+            Task::none()
+        }
+        Message::NameBackspace => {
+            if let Some(name) = state.name_entry.as_mut() {
+                name.pop();
                 state.playground.clear();
             }
 
             Task::none()
         }
+        Message::NameSubmit => {
+            let Some(name) = state.name_entry.take() else {
+                return Task::none();
+            };
+
+            let entry = HighScore {
+                name: clean_name(&name),
+                score: state.game.score,
+                lines: state.game.rows_cleared,
+                level: state.game.level,
+                date: chrono::offset::Local::now(),
+            };
+            state.new_rank = state.high_scores.insert(entry);
+
+            if let Some(path) = &state.high_scores_path
+                && let Err(error) = state.high_scores.save(path)
+            {
+                eprintln!("Could not save the high scores to {}: {}", path.display(), error);
+            }
+
+            state.playground.clear();
+            Task::none()
+        }
+        Message::SaveGame => {
+            if state.name_entry.is_some() {
+                return Task::none();
+            }
+
+            save_game(state);
+
+            // Saving pauses the game, so the replay has to pause here as well.
+            if state.is_running
+                && let Some(recorder) = state.recorder.as_mut()
+            {
+                recorder.record(Event::TogglePause);
+            }
+
+            state.is_running = false;
+            state.last_frame = None;
+            state.playground.clear();
+            Task::none()
+        }
+        Message::PuzzlePrevious | Message::PuzzleNext => {
+            if !state.puzzle_menu {
+                return Task::none();
+            }
+
+            let count = state.puzzles.len();
+            state.puzzle_index = match message {
+                Message::PuzzlePrevious => (state.puzzle_index + count - 1) % count,
+                _ => (state.puzzle_index + 1) % count,
+            };
+
+            show_puzzle(state);
+            Task::none()
+        }
+        Message::PuzzleStart => {
+            if !state.puzzle_menu {
+                return Task::none();
+            }
+
+            state.puzzle_menu = false;
+            state.is_running = true;
+            state.playground.clear();
+            Task::none()
+        }
+        Message::CloseRequested(id) => {
+            save_game(state);
+            window::close(id)
+        }
     }
 }
@@ -3,7 +3,7 @@ use iced::widget::{canvas, container};
 use crate::canvas::State;
 use crate::update::Message;
 
-pub fn view(state: &State) -> Element<Message> {
+pub fn view(state: &State) -> Element<'_, Message> {
     let canvas = canvas(state).width(Fill).height(Fill);
     container(canvas).into()
 }
\ No newline at end of file
@@ -0,0 +1,180 @@
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+use crate::enums::TetrominoKind;
+use crate::randomizer::{Randomizer, random_seed};
+use crate::tetromino::{Tetromino};
+use crate::types::Tetrominos;
+
+/// Bag randomizer: every bag holds `copies` of each piece, so 1 is the 7-bag and 2 the 14-bag.
+/// The shuffles come from a ChaCha8 generator, so the same seed produces the same piece
+/// sequence on every platform and build.
+#[derive(Debug)]
+pub struct Bag {
+    pub items: Tetrominos,
+    copies: usize,
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl Default for Bag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bag {
+    /// A bag with a random seed.
+    pub fn new() -> Self {
+        Bag::with_seed(random_seed())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Bag::with_copies(seed, 1)
+    }
+
+    pub fn with_copies(seed: u64, copies: usize) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let default_items = Bag::create_items(&mut rng, copies);
+
+        Self {
+            items: default_items,
+            copies,
+            seed,
+            rng,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_item(&mut self) -> (Tetromino, Tetromino) {
+        let shape = self.items.pop().unwrap();
+
+        if self.items.is_empty() {
+            self.refill();
+        }
+
+        let next_item = self.items.last().unwrap().clone();
+        
+        (shape, next_item)
+    }
+
+    pub fn refill(&mut self) {
+        self.items = Bag::create_items(&mut self.rng, self.copies);
+    }
+
+    fn create_items(rng: &mut ChaCha8Rng, copies: usize) -> Tetrominos {
+        let mut default_items: Tetrominos = (0..copies)
+            .flat_map(|_| TetrominoKind::ALL.map(Tetromino::from_kind))
+            .collect();
+        default_items.shuffle(rng);
+
+        default_items
+    }
+}
+
+impl Randomizer for Bag {
+    fn next_kind(&mut self) -> TetrominoKind {
+        self.get_item().0.kind
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use super::*;
+
+    fn sequence(bag: &mut Bag, count: usize) -> Vec<TetrominoKind> {
+        (0..count).map(|_| bag.get_item().0.kind).collect()
+    }
+
+    #[test]
+    fn test_shuffle_creates_different_bags() {
+        let mut bag1 = Bag::with_seed(1);
+        let mut bag2 = Bag::with_seed(2);
+        assert_ne!(sequence(&mut bag1, 14), sequence(&mut bag2, 14));
+    }
+
+    #[test]
+    fn test_same_seed_repeats_sequence() {
+        let mut bag1 = Bag::with_seed(42);
+        let mut bag2 = Bag::with_seed(42);
+
+        assert_eq!(bag1.seed(), 42);
+        assert_eq!(sequence(&mut bag1, 70), sequence(&mut bag2, 70));
+    }
+
+    #[test]
+    fn test_seeded_sequence_is_stable() {
+        let mut bag = Bag::with_seed(0);
+        let expected = vec![
+            TetrominoKind::T,
+            TetrominoKind::L,
+            TetrominoKind::Z,
+            TetrominoKind::I,
+            TetrominoKind::J,
+            TetrominoKind::O,
+            TetrominoKind::S,
+        ];
+
+        assert_eq!(sequence(&mut bag, 7), expected);
+    }
+
+    #[test]
+    fn test_refill() {
+        let mut bag = Bag::new();
+        bag.get_item();
+        bag.get_item();
+        assert_eq!(bag.items.len(), 5);
+
+        bag.refill();
+        assert_eq!(bag.items.len(), 7);
+    }
+
+    #[test]
+    fn test_default_bag() {
+        let bag = Bag::new();
+        assert_eq!(bag.items.len(), 7);
+    }
+
+    #[test]
+    fn test_all_shapes_present() {
+        let bag = Bag::new();
+
+        let mut kinds = HashSet::new();
+
+        for shape in &bag.items {
+            for row in &shape.matrix {
+                if let Some(brick) = row.iter().flatten().next() {
+                    kinds.insert(brick.kind);
+                }
+            }
+        }
+
+        assert_eq!(kinds.len(), 7);
+    }
+
+    #[test]
+    fn test_get_item() {
+        let mut bag = Bag::new();
+        let original_length = bag.items.len();
+        assert_eq!(original_length, 7, "A new bag should have 7 items");
+
+        let (_, next_shape) = bag.get_item();
+        assert_eq!(bag.items.len(), 6, "Bag should have 6 items after getting one");
+        assert_eq!(next_shape, bag.items.last().unwrap().clone(), "Next shape should match the last in the bag");
+
+        for i in 0..5 {
+            let (_, _) = bag.get_item();
+            assert_eq!(bag.items.len(), 5-i, "Bag should decrease by one each time");
+        }
+
+        let (_, next_shape_after_refill) = bag.get_item();
+
+        assert_eq!(bag.items.len(), 7, "Bag should refill to 7 items");
+        assert_eq!(next_shape_after_refill, bag.items.last().unwrap().clone(),
+                   "Next shape after refill should match the last in the new bag");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,157 @@
+use crate::enums::TetrominoKind;
+use crate::tetromino::Brick;
+use crate::types::Matrix;
+
+pub fn empty_board(width: usize, height: usize) -> Matrix {
+    vec![vec![None; width]; height]
+}
+
+/// Removes every full row, shifts the rows above down and returns how many were cleared.
+pub fn clear_rows(game_space: &mut Matrix) -> u32 {
+    let cols = game_space[0].len();
+
+    let rows_to_clear: Vec<usize> = game_space
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| row.iter().all(|cell| cell.is_some()))
+        .map(|(row_index, _)| row_index)
+        .collect();
+
+    if !rows_to_clear.is_empty() {
+        for &row_to_clear in rows_to_clear.iter() {
+            game_space.remove(row_to_clear);
+            game_space.insert(0, vec![None; cols]);
+        }
+
+        return rows_to_clear.len() as u32;
+    }
+
+    0
+}
+
+/// Letter of a garbage brick in board text.
+const GARBAGE_LETTER: char = 'G';
+
+/// A board row as text: `.` for an empty cell, `G` for garbage and the piece's letter for
+/// anything else.
+pub fn row_to_text(row: &[Option<Brick>]) -> String {
+    row.iter()
+        .map(|cell| match cell {
+            None => '.',
+            Some(brick) if brick.garbage => GARBAGE_LETTER,
+            Some(brick) => brick.kind.letter(),
+        })
+        .collect()
+}
+
+/// Reads a row written by [`row_to_text`], or returns the first letter it does not know.
+pub fn parse_row(line: &str) -> Result<Vec<Option<Brick>>, char> {
+    line.chars()
+        .map(|cell| match cell {
+            '.' => Ok(None),
+            GARBAGE_LETTER => Ok(Some(Brick::garbage())),
+            letter => TetrominoKind::from_letter(letter).map(|kind| Some(Brick::new(kind))).ok_or(letter),
+        })
+        .collect()
+}
+
+/// Pushes the stack up and fills the bottom with one garbage row per hole, each full but for
+/// its hole, the last one lowest. Returns whether any bricks were pushed off the top.
+pub fn insert_garbage(game_space: &mut Matrix, holes: &[usize]) -> bool {
+    let cols = game_space[0].len();
+    let count = holes.len().min(game_space.len());
+
+    let overflow = game_space
+        .drain(..count)
+        .any(|row| row.iter().any(|cell| cell.is_some()));
+
+    for &hole in &holes[holes.len() - count..] {
+        let mut row = vec![Some(Brick::garbage()); cols];
+        row[hole] = None;
+        game_space.push(row);
+    }
+
+    overflow
+}
+
+/// Number of rows that still hold garbage.
+pub fn garbage_rows(game_space: &Matrix) -> u32 {
+    game_space
+        .iter()
+        .filter(|row| row.iter().flatten().any(|brick| brick.garbage))
+        .count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::TetrominoKind;
+    use crate::tetromino::Brick;
+
+    #[test]
+    fn test_clear_rows_shifts_stack_down() {
+        let brick = Some(Brick::new(TetrominoKind::O));
+
+        let mut game_space: Matrix = vec![
+            vec![None, None, brick, None],
+            vec![brick; 4],
+            vec![brick, None, brick, None],
+            vec![brick; 4],
+        ];
+
+        let cleared = clear_rows(&mut game_space);
+
+        let expected_game_space: Matrix = vec![
+            vec![None; 4],
+            vec![None; 4],
+            vec![None, None, brick, None],
+            vec![brick, None, brick, None],
+        ];
+
+        assert_eq!(cleared, 2);
+        assert_eq!(game_space, expected_game_space);
+    }
+
+    #[test]
+    fn test_insert_garbage_pushes_stack_up() {
+        let brick = Some(Brick::new(TetrominoKind::O));
+        let garbage = Some(Brick::garbage());
+
+        let mut game_space: Matrix = vec![vec![None; 4], vec![None; 4], vec![brick, None, None, None]];
+
+        assert!(!insert_garbage(&mut game_space, &[1, 3]));
+
+        let expected_game_space: Matrix = vec![
+            vec![brick, None, None, None],
+            vec![garbage, None, garbage, garbage],
+            vec![garbage, garbage, garbage, None],
+        ];
+
+        assert_eq!(game_space, expected_game_space);
+        assert_eq!(garbage_rows(&game_space), 2);
+
+        assert!(insert_garbage(&mut game_space, &[0]));
+        assert_eq!(garbage_rows(&game_space), 3);
+    }
+
+    #[test]
+    fn test_garbage_rows_count_partly_dug_rows() {
+        let brick = Some(Brick::new(TetrominoKind::O));
+        let garbage = Some(Brick::garbage());
+
+        let game_space: Matrix = vec![vec![brick, brick, None, None], vec![garbage, None, brick, None], vec![None; 4]];
+
+        assert_eq!(garbage_rows(&game_space), 1);
+    }
+
+    #[test]
+    fn test_clear_rows_without_full_rows() {
+        let brick = Some(Brick::new(TetrominoKind::O));
+
+        let mut game_space: Matrix = vec![vec![None; 4], vec![brick, brick, None, brick]];
+        let expected_game_space = game_space.clone();
+
+        assert_eq!(clear_rows(&mut game_space), 0);
+        assert_eq!(game_space, expected_game_space);
+    }
+}
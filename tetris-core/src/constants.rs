@@ -0,0 +1,38 @@
+/// Default board size; any size from `MIN_BOARD_*` to `MAX_BOARD_*` can be played.
+pub const BOARD_WIDTH: usize = 10;
+pub const BOARD_HEIGHT: usize = 20;
+/// Hidden rows above the visible board. Pieces spawn in the bottom two of them, rows 21 and 22
+/// counted from the floor of a 20-row board.
+pub const BUFFER_HEIGHT: usize = 20;
+pub const MIN_BOARD_WIDTH: usize = 4;
+pub const MIN_BOARD_HEIGHT: usize = 4;
+pub const MAX_BOARD_WIDTH: usize = 40;
+pub const MAX_BOARD_HEIGHT: usize = 100;
+pub const DEFAULT_LEVEL: u32 = 0;
+/// Highest level a marathon can be started at.
+pub const MAX_START_LEVEL: u32 = 29;
+pub const DEFAULT_LOCK_DELAY_MS: u64 = 500;
+pub const MAX_LOCK_RESETS: u32 = 15;
+pub const MAX_PREVIEW_COUNT: usize = 6;
+/// Lines to clear in a sprint.
+pub const SPRINT_LINES: u32 = 40;
+/// Length of an ultra game, in minutes.
+pub const MIN_ULTRA_MINUTES: u32 = 2;
+pub const MAX_ULTRA_MINUTES: u32 = 3;
+pub const DEFAULT_ULTRA_MINUTES: u32 = 3;
+/// Garbage rows a dig starts with; boards keep at least one row free of garbage.
+pub const DEFAULT_DIG_ROWS: usize = 10;
+pub const MAX_DIG_ROWS: usize = MAX_BOARD_HEIGHT - 1;
+/// Chance in percent that a garbage hole moves away from the one above it.
+pub const DEFAULT_MESSINESS: u32 = 100;
+pub const MAX_MESSINESS: u32 = 100;
+/// Master gravity is counted in 1/`GRAVITY_UNIT` rows per frame, so `GRAVITY_UNIT` is 1G.
+pub const GRAVITY_UNIT: u32 = 256;
+/// Gravity at which a piece reaches the floor in the frame it appears.
+pub const TWENTY_G: u32 = 20 * GRAVITY_UNIT;
+/// Frames per second that master gravity and delays are timed in.
+pub const FRAMES_PER_SECOND: u64 = 60;
+/// Levels of a master section; only line clears take the level past the last one of a section.
+pub const MASTER_SECTION_LEVELS: u32 = 100;
+/// Level that completes a master game.
+pub const MASTER_MAX_LEVEL: u32 = 999;
@@ -0,0 +1,70 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TetrominoKind {
+    #[default]
+    O,
+    I,
+    S,
+    Z,
+    T,
+    L,
+    J,
+}
+
+impl TetrominoKind {
+    pub const ALL: [TetrominoKind; 7] = [
+        TetrominoKind::O,
+        TetrominoKind::I,
+        TetrominoKind::S,
+        TetrominoKind::Z,
+        TetrominoKind::T,
+        TetrominoKind::L,
+        TetrominoKind::J,
+    ];
+
+    /// Letter used in saved games and puzzle files.
+    pub fn letter(self) -> char {
+        match self {
+            TetrominoKind::O => 'O',
+            TetrominoKind::I => 'I',
+            TetrominoKind::S => 'S',
+            TetrominoKind::Z => 'Z',
+            TetrominoKind::T => 'T',
+            TetrominoKind::L => 'L',
+            TetrominoKind::J => 'J',
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<Self> {
+        TetrominoKind::ALL.into_iter().find(|kind| kind.letter() == letter)
+    }
+}
+
+/// SRS rotation states: spawn (0), one turn right (R), two turns (2) and one turn left (L).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RotationState {
+    #[default]
+    Zero,
+    Right,
+    Two,
+    Left,
+}
+
+impl RotationState {
+    pub fn clockwise(self) -> Self {
+        match self {
+            RotationState::Zero => RotationState::Right,
+            RotationState::Right => RotationState::Two,
+            RotationState::Two => RotationState::Left,
+            RotationState::Left => RotationState::Zero,
+        }
+    }
+
+    pub fn counterclockwise(self) -> Self {
+        match self {
+            RotationState::Zero => RotationState::Left,
+            RotationState::Left => RotationState::Two,
+            RotationState::Two => RotationState::Right,
+            RotationState::Right => RotationState::Zero,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::board::empty_board;
+use crate::constants::{BUFFER_HEIGHT, MIN_BOARD_HEIGHT};
+use crate::enums::RotationState;
+use crate::piece::ActivePiece;
+use crate::rotations::{rotate_clockwise, rotate_counterclockwise};
+use crate::types::Matrix;
+
+/// Columns and shape of a piece with its height taken out, which is all a hard drop keeps.
+fn footprint(piece: &ActivePiece) -> Vec<(isize, isize)> {
+    let cells = piece.cells();
+    let top = cells.iter().map(|&(row, _)| row).min().unwrap_or(0);
+
+    let mut footprint: Vec<(isize, isize)> = cells.iter().map(|&(row, col)| (row - top, col)).collect();
+    footprint.sort();
+    footprint
+}
+
+/// Fewest inputs that bring a freshly spawned piece on an empty board `cols` wide into the
+/// columns and orientation of `target`. A rotation is one input and so is a shift of any
+/// length, since holding a direction reaches the wall with a single key press.
+pub fn min_inputs(target: &ActivePiece, cols: usize) -> Option<u32> {
+    let game_space: Matrix = empty_board(cols, BUFFER_HEIGHT + MIN_BOARD_HEIGHT);
+    let goal = footprint(target);
+
+    let start = ActivePiece::spawn(target.kind, RotationState::Zero, cols);
+    let mut seen = HashSet::from([(start.x, start.y, start.rotation as u8)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((piece, inputs)) = queue.pop_front() {
+        if footprint(&piece) == goal {
+            return Some(inputs);
+        }
+
+        let mut next = Vec::new();
+
+        for rotate in [rotate_clockwise, rotate_counterclockwise] {
+            let mut rotated = piece;
+            if rotate(&game_space, &mut rotated).is_some() {
+                next.push(rotated);
+            }
+        }
+
+        for dx in [-1, 1] {
+            let mut shifted = piece.shifted(dx, 0);
+
+            while shifted.fits(&game_space) {
+                next.push(shifted);
+                shifted = shifted.shifted(dx, 0);
+            }
+        }
+
+        for piece in next {
+            if seen.insert((piece.x, piece.y, piece.rotation as u8)) {
+                queue.push_back((piece, inputs + 1));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::TetrominoKind;
+
+    fn placed(kind: TetrominoKind, rotation: RotationState, x: isize) -> ActivePiece {
+        ActivePiece::new(kind, rotation, x, 30)
+    }
+
+    #[test]
+    fn test_spawn_position_needs_no_input() {
+        for kind in TetrominoKind::ALL {
+            let spawn = ActivePiece::spawn(kind, RotationState::Zero, 10);
+            assert_eq!(min_inputs(&spawn.shifted(0, 12), 10), Some(0), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_shifts_and_rotations() {
+        // Any distance to one side is a single shift.
+        assert_eq!(min_inputs(&placed(TetrominoKind::T, RotationState::Zero, 0), 10), Some(1));
+        assert_eq!(min_inputs(&placed(TetrominoKind::T, RotationState::Zero, 4), 10), Some(1));
+
+        assert_eq!(min_inputs(&placed(TetrominoKind::T, RotationState::Right, 3), 10), Some(1));
+        assert_eq!(min_inputs(&placed(TetrominoKind::T, RotationState::Two, 3), 10), Some(2));
+        assert_eq!(min_inputs(&placed(TetrominoKind::T, RotationState::Left, 0), 10), Some(2));
+    }
+
+    #[test]
+    fn test_equivalent_orientations() {
+        // A vertical I in state L at x 0 covers column 1, just like state R at x -1.
+        let left = placed(TetrominoKind::I, RotationState::Left, 0);
+        let right = placed(TetrominoKind::I, RotationState::Right, -1);
+
+        assert_eq!(min_inputs(&left, 10), min_inputs(&right, 10));
+        assert_eq!(min_inputs(&placed(TetrominoKind::O, RotationState::Two, 4), 10), Some(0));
+    }
+}
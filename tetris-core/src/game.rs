@@ -0,0 +1,1486 @@
+use crate::board::{clear_rows, empty_board, garbage_rows, insert_garbage};
+use std::collections::VecDeque;
+
+use crate::constants::{
+    BOARD_HEIGHT, BOARD_WIDTH, BUFFER_HEIGHT, DEFAULT_DIG_ROWS, DEFAULT_LEVEL, DEFAULT_MESSINESS,
+    DEFAULT_ULTRA_MINUTES, FRAMES_PER_SECOND, GRAVITY_UNIT, MASTER_MAX_LEVEL, MASTER_SECTION_LEVELS,
+    MAX_PREVIEW_COUNT, MIN_BOARD_HEIGHT, MIN_BOARD_WIDTH, SPRINT_LINES, TWENTY_G,
+};
+use crate::garbage::GarbageHoles;
+use crate::finesse::min_inputs;
+use crate::level::{get_garbage_interval_by_level, get_level_from, get_speed_by_level};
+use crate::lock_delay::LockDelay;
+use crate::master::{get_delays, get_gravity, get_next_level};
+use crate::mode::{MarathonGoal, Mode};
+use crate::moves::{
+    drop_distance, is_block_out, is_lock_out, lock_piece, move_bottom, move_left, move_right,
+};
+use crate::piece::ActivePiece;
+use crate::puzzle::Puzzle;
+use crate::randomizer::{Randomizer, RandomizerKind, Scripted, random_seed};
+use crate::rotations::{rotate_clockwise, rotate_counterclockwise};
+use crate::scoring::{ScoreEvent, Scoring, Spin, detect_t_spin, is_perfect_clear};
+use crate::tetromino::Tetromino;
+use crate::types::Matrix;
+
+/// A single player action or the gravity step that moves the piece down on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Left,
+    Right,
+    SoftDrop,
+    HardDrop,
+    RotateClockwise,
+    RotateCounterclockwise,
+    Hold,
+    Gravity,
+}
+
+/// Why a game ended, following the guideline's top out rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopOut {
+    /// A new piece overlapped the stack where it spawns.
+    BlockOut,
+    /// A piece locked entirely inside the hidden buffer zone.
+    LockOut,
+    /// Rising garbage pushed bricks or the active piece out of the top of the buffer zone.
+    PushOut,
+}
+
+/// Everything that is chosen before a game starts and kept when it restarts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameOptions {
+    /// Number of columns on the board.
+    pub width: usize,
+    /// Number of visible rows on the board.
+    pub height: usize,
+    pub randomizer: RandomizerKind,
+    pub mode: Mode,
+    /// Length of an ultra game.
+    pub ultra_minutes: u32,
+    pub marathon_goal: MarathonGoal,
+    /// Level a marathon starts at; the other modes always start at `DEFAULT_LEVEL`.
+    pub start_level: u32,
+    /// Garbage rows a dig starts with, at most one less than the board is high.
+    pub dig_rows: usize,
+    /// Chance in percent that a garbage hole of a dig or survival is not under the one above it.
+    pub messiness: u32,
+    /// Whether pieces fall on their own in zen; every other mode has gravity.
+    pub gravity: bool,
+}
+
+impl GameOptions {
+    /// Lines that complete the game, if there is such a goal.
+    pub fn line_goal(&self) -> Option<u32> {
+        match self.mode {
+            Mode::Marathon => self.marathon_goal.lines(),
+            Mode::Sprint => Some(SPRINT_LINES),
+            Mode::Ultra | Mode::Dig | Mode::Survival | Mode::Master | Mode::Zen | Mode::Puzzle => None,
+        }
+    }
+
+    pub fn start_level(&self) -> u32 {
+        match self.mode {
+            Mode::Marathon => self.start_level,
+            Mode::Sprint | Mode::Ultra | Mode::Dig | Mode::Survival | Mode::Master | Mode::Zen | Mode::Puzzle => {
+                DEFAULT_LEVEL
+            }
+        }
+    }
+
+    /// Garbage rows the board starts with.
+    pub fn garbage_rows(&self) -> usize {
+        match self.mode {
+            Mode::Dig => self.dig_rows.min(self.height - 1),
+            _ => 0,
+        }
+    }
+
+    /// Whether pieces fall on their own. Without gravity they only move down when dropped.
+    pub fn has_gravity(&self) -> bool {
+        self.mode != Mode::Zen || self.gravity
+    }
+
+    /// Play time after which the game ends, for modes played against the clock.
+    pub fn time_limit_ms(&self) -> Option<u64> {
+        (self.mode == Mode::Ultra).then_some(self.ultra_minutes as u64 * 60_000)
+    }
+}
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        Self {
+            width: BOARD_WIDTH,
+            height: BOARD_HEIGHT,
+            randomizer: RandomizerKind::default(),
+            mode: Mode::default(),
+            ultra_minutes: DEFAULT_ULTRA_MINUTES,
+            marathon_goal: MarathonGoal::default(),
+            start_level: DEFAULT_LEVEL,
+            dig_rows: DEFAULT_DIG_ROWS,
+            messiness: DEFAULT_MESSINESS,
+            gravity: true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    /// The visible board below `BUFFER_HEIGHT` hidden rows.
+    pub game_space: Matrix,
+    pub randomizer: Box<dyn Randomizer>,
+    pub active_piece: Option<ActivePiece>,
+    /// Upcoming pieces, always `MAX_PREVIEW_COUNT` long so the sequence does not depend on how
+    /// many of them a front end shows.
+    pub next_items: VecDeque<Tetromino>,
+    pub hold_item: Option<Tetromino>,
+    pub hold_used: bool,
+    pub lock_delay: LockDelay,
+    pub level: u32,
+    pub rows_cleared: u32,
+    pub score: u32,
+    pub scoring: Scoring,
+    /// The most recent lock worth announcing, such as a Tetris or a T-spin.
+    pub last_event: Option<ScoreEvent>,
+    pub game_over: bool,
+    pub top_out: Option<TopOut>,
+    /// Set when the game ended by reaching the goal of its mode or running out of time rather
+    /// than by topping out.
+    pub completed: bool,
+    /// Play time, counted by [`Game::advance`].
+    pub elapsed_ms: u64,
+    pub pieces_placed: u32,
+    /// Inputs spent beyond the fewest that would have placed each piece, summed over the game.
+    pub finesse_faults: u32,
+    /// `elapsed_ms` at which each line was cleared, one entry per line.
+    pub line_times: Vec<u64>,
+    /// Time since the last garbage row rose in survival.
+    pub garbage_timer_ms: u64,
+    /// Time left before the next piece appears, in modes with entry and line clear delays.
+    pub entry_delay_ms: Option<u64>,
+    /// The puzzle being solved; its pieces replace the randomizer.
+    pub puzzle: Option<Puzzle>,
+    /// Kick index of the last successful action when it was a rotation, for T-spin detection.
+    last_kick: Option<usize>,
+    /// Inputs spent on the active piece so far, for finesse.
+    piece_inputs: u32,
+    /// The previous input on the active piece, so holding a direction counts as one shift.
+    last_input: Option<Input>,
+    pub(crate) options: GameOptions,
+    pub(crate) seed: u64,
+    /// How many pieces the randomizer has produced, so a saved game can rebuild its state.
+    pub(crate) pieces_drawn: u64,
+    pub(crate) garbage_holes: GarbageHoles,
+    /// How many garbage rows have been inserted, so a saved game can rebuild `garbage_holes`.
+    pub(crate) garbage_drawn: u64,
+    /// Gravity towards the next row, in `GRAVITY_UNIT`ths of a row times milliseconds per
+    /// second, for modes with frame gravity.
+    pub(crate) gravity_progress: u64,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    /// A game with a random seed.
+    pub fn new() -> Self {
+        Self::with_seed(random_seed())
+    }
+
+    /// A 7-bag game whose piece sequence is fully determined by `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_randomizer(RandomizerKind::default(), seed)
+    }
+
+    pub fn with_randomizer(randomizer: RandomizerKind, seed: u64) -> Self {
+        Self::with_options(
+            GameOptions {
+                randomizer,
+                ..GameOptions::default()
+            },
+            seed,
+        )
+    }
+
+    /// A game with the board size, randomizer and mode of `options`.
+    ///
+    /// Panics when the board is smaller than `MIN_BOARD_WIDTH` by `MIN_BOARD_HEIGHT`, which
+    /// is the least every piece needs to spawn and rotate.
+    pub fn with_options(options: GameOptions, seed: u64) -> Self {
+        let game_space = empty_board(options.width, options.height + BUFFER_HEIGHT);
+        let randomizer = options.randomizer.create(seed);
+        let mut game = Self::with_board(options, seed, randomizer, game_space, None);
+
+        let holes = game.garbage_holes.next_holes(options.garbage_rows(), options.width);
+        game.garbage_drawn = holes.len() as u64;
+        insert_garbage(&mut game.game_space, &holes);
+
+        let item = game.take_next_item();
+        game.spawn_item(&item);
+
+        game
+    }
+
+    /// A game of `puzzle`: its board, and its pieces dealt in order instead of by a randomizer.
+    pub fn from_puzzle(puzzle: Puzzle) -> Self {
+        let options = GameOptions {
+            width: puzzle.width(),
+            height: puzzle.height(),
+            mode: Mode::Puzzle,
+            ..GameOptions::default()
+        };
+
+        let mut game_space = empty_board(puzzle.width(), BUFFER_HEIGHT);
+        game_space.extend(puzzle.board.iter().cloned());
+        let randomizer = Box::new(Scripted::new(puzzle.pieces.clone()));
+
+        let mut game = Self::with_board(options, 0, randomizer, game_space, Some(puzzle));
+
+        let item = game.take_next_item();
+        game.spawn_item(&item);
+
+        game
+    }
+
+    /// A game on `game_space` with its queue filled from `randomizer` and no piece spawned yet.
+    fn with_board(
+        options: GameOptions,
+        seed: u64,
+        mut randomizer: Box<dyn Randomizer>,
+        game_space: Matrix,
+        puzzle: Option<Puzzle>,
+    ) -> Self {
+        let GameOptions { width, height, .. } = options;
+
+        assert!(
+            width >= MIN_BOARD_WIDTH && height >= MIN_BOARD_HEIGHT,
+            "a {}x{} board is too small",
+            width,
+            height
+        );
+
+        let next_items = (0..MAX_PREVIEW_COUNT)
+            .map(|_| Tetromino::from_kind(randomizer.next_kind()))
+            .collect();
+
+        Self {
+            game_space,
+            randomizer,
+            options,
+            seed,
+            pieces_drawn: MAX_PREVIEW_COUNT as u64,
+            active_piece: None,
+            next_items,
+            hold_item: None,
+            hold_used: false,
+            lock_delay: LockDelay::default(),
+            level: options.start_level(),
+            rows_cleared: 0,
+            score: 0,
+            scoring: Scoring::default(),
+            last_event: None,
+            game_over: false,
+            top_out: None,
+            completed: false,
+            elapsed_ms: 0,
+            pieces_placed: 0,
+            finesse_faults: 0,
+            line_times: Vec::new(),
+            garbage_timer_ms: 0,
+            entry_delay_ms: None,
+            puzzle,
+            gravity_progress: 0,
+            garbage_holes: GarbageHoles::new(seed, options.messiness),
+            garbage_drawn: 0,
+            last_kick: None,
+            piece_inputs: 0,
+            last_input: None,
+        }
+    }
+
+    /// Starts over with an empty board and a fresh randomizer under the same options, keeping
+    /// the lock delay settings.
+    pub fn restart(&mut self) {
+        self.restart_with_seed(random_seed());
+    }
+
+    /// Like [`Game::restart`], but with the piece sequence of `seed`. A puzzle starts over
+    /// from its own board and pieces instead.
+    pub fn restart_with_seed(&mut self, seed: u64) {
+        let lock_delay = LockDelay::new(self.lock_delay.delay_ms, self.lock_delay.max_resets);
+
+        *self = match self.puzzle.take() {
+            Some(puzzle) => Self::from_puzzle(puzzle),
+            None => Self::with_options(self.options, seed),
+        };
+        self.lock_delay = lock_delay;
+    }
+
+    /// Number of columns on the board.
+    pub fn width(&self) -> usize {
+        self.game_space[0].len()
+    }
+
+    /// Number of visible rows on the board.
+    pub fn height(&self) -> usize {
+        self.game_space.len() - BUFFER_HEIGHT
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn randomizer_kind(&self) -> RandomizerKind {
+        self.options.randomizer
+    }
+
+    pub fn options(&self) -> GameOptions {
+        self.options
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.options.mode
+    }
+
+    /// Rows that still hold garbage.
+    pub fn garbage_remaining(&self) -> u32 {
+        garbage_rows(&self.game_space)
+    }
+
+    /// Milliseconds between two garbage rows rising at the current level, in modes where they
+    /// rise.
+    pub fn garbage_interval_ms(&self) -> Option<u64> {
+        (self.options.mode == Mode::Survival).then(|| get_garbage_interval_by_level(self.level))
+    }
+
+    /// Milliseconds until the next garbage row rises.
+    pub fn next_garbage_ms(&self) -> Option<u64> {
+        self.garbage_interval_ms()
+            .map(|interval| interval.saturating_sub(self.garbage_timer_ms))
+    }
+
+    /// Gravity in `GRAVITY_UNIT`ths of a row per frame, in modes where it is counted in G.
+    pub fn gravity(&self) -> Option<u32> {
+        self.options.mode.has_frame_gravity().then(|| get_gravity(self.level))
+    }
+
+    /// Milliseconds between two gravity steps at the current level, at least one.
+    pub fn tick_rate_ms(&self) -> u64 {
+        match self.gravity() {
+            Some(gravity) => (GRAVITY_UNIT as u64 * 1000 / (FRAMES_PER_SECOND * gravity as u64)).max(1),
+            None => get_speed_by_level(self.level),
+        }
+    }
+
+    pub fn step(&mut self, input: Input) {
+        if self.game_over {
+            return;
+        }
+
+        if input == Input::Hold {
+            self.hold();
+            return;
+        }
+
+        if input == Input::Gravity && !self.options.has_gravity() {
+            return;
+        }
+
+        self.count_input(input);
+
+        let Some(piece) = self.active_piece.as_mut() else {
+            return;
+        };
+
+        let (moved, kick) = match input {
+            Input::Left => (move_left(&self.game_space, piece), None),
+            Input::Right => (move_right(&self.game_space, piece), None),
+            Input::RotateClockwise => {
+                let kick = rotate_clockwise(&self.game_space, piece);
+                (kick.is_some(), kick)
+            }
+            Input::RotateCounterclockwise => {
+                let kick = rotate_counterclockwise(&self.game_space, piece);
+                (kick.is_some(), kick)
+            }
+            Input::SoftDrop | Input::Gravity => {
+                if move_bottom(&self.game_space, piece) {
+                    self.lock_delay.fell(piece.y);
+                    self.last_kick = None;
+
+                    if input == Input::SoftDrop {
+                        self.score = self.score.saturating_add(1);
+                    }
+                } else if self.lock_delay.delay_ms == 0 {
+                    self.lock_item();
+                }
+
+                (false, None)
+            }
+            Input::HardDrop => {
+                let dropped_rows = drop_distance(&self.game_space, piece);
+                *piece = piece.shifted(0, dropped_rows as isize);
+
+                if dropped_rows > 0 {
+                    self.last_kick = None;
+                }
+
+                self.score = self.score.saturating_add(dropped_rows as u32 * 2);
+                self.lock_item();
+
+                (false, None)
+            }
+            Input::Hold => (false, None),
+        };
+
+        if moved {
+            self.last_kick = kick;
+            self.drop_at_twenty_g();
+
+            let grounded = self.is_grounded();
+            self.lock_delay.moved(grounded);
+        }
+    }
+
+    /// Lets `elapsed_ms` of lock delay pass. Returns true when the piece got locked. Ends the
+    /// game when it runs out of time, raises the garbage that is due, counts down the entry
+    /// delay and applies gravity counted in G.
+    pub fn advance(&mut self, elapsed_ms: u64) -> bool {
+        if self.game_over {
+            return false;
+        }
+
+        self.elapsed_ms += elapsed_ms;
+
+        if let Some(limit) = self.options.time_limit_ms()
+            && self.elapsed_ms >= limit
+        {
+            self.elapsed_ms = limit;
+            self.complete();
+            return false;
+        }
+
+        if let Some(interval) = self.garbage_interval_ms() {
+            self.garbage_timer_ms += elapsed_ms;
+
+            while self.garbage_timer_ms >= interval && !self.game_over {
+                self.garbage_timer_ms -= interval;
+                self.raise_garbage();
+            }
+        }
+
+        if let Some(delay_ms) = self.entry_delay_ms {
+            if elapsed_ms < delay_ms {
+                self.entry_delay_ms = Some(delay_ms - elapsed_ms);
+                return false;
+            }
+
+            self.entry_delay_ms = None;
+            self.spawn_next_item();
+            return false;
+        }
+
+        if self.active_piece.is_none() {
+            return false;
+        }
+
+        if let Some(gravity) = self.gravity() {
+            self.fall(elapsed_ms, gravity);
+        }
+
+        let grounded = self.is_grounded();
+
+        if self.lock_delay.advance(elapsed_ms, grounded) {
+            self.lock_item();
+            return true;
+        }
+
+        false
+    }
+
+    /// The first `count` upcoming items, fewer when a puzzle has fewer pieces left.
+    pub fn preview(&self, count: usize) -> impl Iterator<Item = &Tetromino> {
+        let count = count.min(self.pieces_left().unwrap_or(usize::MAX));
+
+        self.next_items.iter().take(count)
+    }
+
+    /// Pieces of a puzzle that have not been dealt yet.
+    pub fn pieces_left(&self) -> Option<usize> {
+        let dealt = (self.pieces_drawn as usize).saturating_sub(MAX_PREVIEW_COUNT);
+
+        self.puzzle.as_ref().map(|puzzle| puzzle.pieces.len().saturating_sub(dealt))
+    }
+
+    /// Whether the active piece rests on the floor or the stack.
+    pub fn is_grounded(&self) -> bool {
+        self.active_piece
+            .is_some_and(|piece| drop_distance(&self.game_space, &piece) == 0)
+    }
+
+    /// Puts `item` above the visible board as the active piece. Ends the game with a block out
+    /// instead when the spawn area is already taken.
+    fn spawn_item(&mut self, item: &Tetromino) -> bool {
+        let piece = ActivePiece::spawn(item.kind, item.rotation, self.width());
+
+        if is_block_out(&self.game_space, &piece) && self.top_out(TopOut::BlockOut) {
+            return false;
+        }
+
+        if self.options.mode == Mode::Master {
+            self.lock_delay.delay_ms = get_delays(self.level).lock_delay_ms();
+            self.lock_delay.max_resets = 0;
+        }
+
+        self.active_piece = Some(piece);
+        self.lock_delay.start(piece.y);
+        self.last_kick = None;
+        self.piece_inputs = 0;
+        self.last_input = None;
+        self.drop_at_twenty_g();
+        true
+    }
+
+    fn spawn_next_item(&mut self) {
+        let item = self.take_next_item();
+
+        if self.spawn_item(&item) {
+            self.hold_used = false;
+        }
+    }
+
+    /// Moves the active piece down by as many rows as `gravity` covers in `elapsed_ms`, down to
+    /// the stack at most.
+    fn fall(&mut self, elapsed_ms: u64, gravity: u32) {
+        let per_row = GRAVITY_UNIT as u64 * 1000;
+
+        self.gravity_progress += elapsed_ms * gravity as u64 * FRAMES_PER_SECOND;
+        let rows = self.gravity_progress / per_row;
+        self.gravity_progress %= per_row;
+
+        let Some(piece) = self.active_piece.as_mut() else {
+            return;
+        };
+
+        let rows = rows.min(drop_distance(&self.game_space, piece) as u64);
+
+        if rows > 0 {
+            *piece = piece.shifted(0, rows as isize);
+            self.lock_delay.fell(piece.y);
+            self.last_kick = None;
+        }
+    }
+
+    /// At 20G a piece never hangs in the air: it lands the moment it appears or moves.
+    fn drop_at_twenty_g(&mut self) {
+        if self.gravity().is_none_or(|gravity| gravity < TWENTY_G) {
+            return;
+        }
+
+        if let Some(piece) = self.active_piece.as_mut() {
+            *piece = piece.shifted(0, drop_distance(&self.game_space, piece) as isize);
+            self.lock_delay.fell(piece.y);
+        }
+    }
+
+    /// Counts a player input towards the active piece's finesse. Gravity and drops are free,
+    /// and repeated shifts in one direction count once, as holding the key would do them.
+    fn count_input(&mut self, input: Input) {
+        match input {
+            Input::Left | Input::Right if self.last_input == Some(input) => {}
+            Input::Left | Input::Right | Input::RotateClockwise | Input::RotateCounterclockwise => {
+                self.piece_inputs += 1;
+            }
+            _ => {}
+        }
+
+        if input != Input::Gravity {
+            self.last_input = Some(input);
+        }
+    }
+
+    fn lock_item(&mut self) {
+        let mut spin = Spin::None;
+
+        if let Some(piece) = self.active_piece.take() {
+            if let Some(kick) = self.last_kick {
+                spin = detect_t_spin(&self.game_space, &piece, kick);
+            }
+
+            lock_piece(&mut self.game_space, &piece);
+
+            if is_lock_out(&piece) && self.top_out(TopOut::LockOut) {
+                return;
+            }
+
+            self.pieces_placed += 1;
+
+            if let Some(fewest) = min_inputs(&piece, self.width()) {
+                self.finesse_faults += self.piece_inputs.saturating_sub(fewest);
+            }
+        }
+
+        let cleared_rows = clear_rows(&mut self.game_space);
+        let perfect_clear = cleared_rows > 0 && is_perfect_clear(&self.game_space);
+
+        // Master levels go up to 999, so they score by section.
+        let scoring_level = match self.options.mode {
+            Mode::Master => self.level / MASTER_SECTION_LEVELS,
+            _ => self.level,
+        };
+        let event = self.scoring.score_lock(cleared_rows, spin, perfect_clear, scoring_level);
+        self.score = self.score.saturating_add(event.points);
+
+        if event.label().is_some() {
+            self.last_event = Some(event);
+        }
+
+        self.rows_cleared += cleared_rows;
+        self.line_times.extend((0..cleared_rows).map(|_| self.elapsed_ms));
+
+        let delays = get_delays(self.level);
+
+        if self.options.mode == Mode::Master {
+            self.level = get_next_level(self.level, cleared_rows);
+        } else if self.options.mode.levels_up() {
+            self.level = get_level_from(self.options.start_level(), self.rows_cleared);
+        }
+
+        let solved = self
+            .puzzle
+            .as_ref()
+            .is_some_and(|puzzle| puzzle.goal.is_met(&event, self.rows_cleared));
+
+        if self.goal_reached() || solved {
+            self.complete();
+            return;
+        }
+
+        // A puzzle out of pieces still gets to play the one on hold.
+        if self.pieces_left() == Some(0) {
+            if let Some(item) = self.hold_item.take() {
+                self.spawn_item(&item);
+            } else {
+                self.fail();
+            }
+            return;
+        }
+
+        if self.options.mode == Mode::Master {
+            self.entry_delay_ms = Some(delays.entry_delay_ms(cleared_rows));
+            return;
+        }
+
+        self.spawn_next_item();
+    }
+
+    /// Pushes the stack up by one garbage row. The active piece keeps its place unless the row
+    /// would overlap it, in which case it is pushed up as well. Tops out when bricks or the
+    /// piece are pushed out of the top of the buffer zone.
+    fn raise_garbage(&mut self) {
+        let holes = self.garbage_holes.next_holes(1, self.width());
+        self.garbage_drawn += 1;
+
+        if insert_garbage(&mut self.game_space, &holes) && self.top_out(TopOut::PushOut) {
+            return;
+        }
+
+        let Some(piece) = self.active_piece else {
+            return;
+        };
+
+        if piece.fits(&self.game_space) {
+            return;
+        }
+
+        // Everything below the piece moved up one row, so one row up it fits again unless it
+        // would leave the board.
+        let raised = piece.shifted(0, -1);
+
+        if raised.fits(&self.game_space) {
+            self.active_piece = Some(raised);
+        } else {
+            self.top_out(TopOut::PushOut);
+        }
+    }
+
+    /// Whether the game's mode is won: enough lines cleared, all garbage dug out or the last
+    /// master level reached.
+    fn goal_reached(&self) -> bool {
+        match self.options.mode {
+            Mode::Dig => self.garbage_remaining() == 0,
+            Mode::Master => self.level >= MASTER_MAX_LEVEL,
+            _ => self.options.line_goal().is_some_and(|goal| self.rows_cleared >= goal),
+        }
+    }
+
+    /// Ends the game, or clears the board in modes that play on after topping out. Returns
+    /// whether the game ended.
+    fn top_out(&mut self, top_out: TopOut) -> bool {
+        if self.options.mode.tops_out() {
+            self.end(top_out);
+            return true;
+        }
+
+        self.game_space = empty_board(self.width(), self.game_space.len());
+        false
+    }
+
+    fn end(&mut self, top_out: TopOut) {
+        self.active_piece = None;
+        self.game_over = true;
+        self.top_out = Some(top_out);
+    }
+
+    /// Ends the game without reaching its goal, as when a puzzle runs out of pieces.
+    fn fail(&mut self) {
+        self.active_piece = None;
+        self.game_over = true;
+    }
+
+    /// Ends the game because its goal was reached.
+    fn complete(&mut self) {
+        self.active_piece = None;
+        self.game_over = true;
+        self.completed = true;
+    }
+
+    /// Takes the first upcoming item and draws a new one from the randomizer to replace it.
+    fn take_next_item(&mut self) -> Tetromino {
+        let next_item = Tetromino::from_kind(self.randomizer.next_kind());
+        self.pieces_drawn += 1;
+        self.next_items.push_back(next_item);
+        self.next_items.pop_front().unwrap()
+    }
+
+    fn hold(&mut self) {
+        // A puzzle's last piece has nothing to be swapped for.
+        if self.hold_used || (self.hold_item.is_none() && self.pieces_left() == Some(0)) {
+            return;
+        }
+
+        let Some(piece) = self.active_piece.take() else {
+            return;
+        };
+
+        let held_item = Tetromino::from_kind(piece.kind);
+
+        let item = match self.hold_item.replace(held_item) {
+            Some(item) => item,
+            None => self.take_next_item(),
+        };
+
+        if self.spawn_item(&item) {
+            self.hold_used = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{RotationState, TetrominoKind};
+    use crate::constants::MAX_LOCK_RESETS;
+    use crate::tetromino::Brick;
+
+    #[test]
+    fn test_new_game_spawns_piece() {
+        let game = Game::new();
+
+        assert!(game.active_piece.is_some());
+        assert!(!game.game_over);
+        assert_eq!(game.game_space.len(), BUFFER_HEIGHT + BOARD_HEIGHT);
+        assert_eq!(game.game_space[0].len(), BOARD_WIDTH);
+        assert_eq!((game.width(), game.height()), (BOARD_WIDTH, BOARD_HEIGHT));
+    }
+
+    #[test]
+    fn test_board_size() {
+        for (width, height) in [(4, 20), (10, 40), (20, 20), (MIN_BOARD_WIDTH, MIN_BOARD_HEIGHT)] {
+            let options = GameOptions {
+                width,
+                height,
+                ..GameOptions::default()
+            };
+            let mut game = Game::with_options(options, 5);
+
+            assert_eq!((game.width(), game.height()), (width, height));
+
+            for _ in 0..100 {
+                let Some(piece) = game.active_piece else {
+                    break;
+                };
+
+                assert!(piece.fits(&game.game_space), "{:?} on a {}x{} board", piece, width, height);
+                game.step(Input::HardDrop);
+            }
+
+            assert!(game.game_over, "{}x{} board never filled up", width, height);
+
+            game.restart_with_seed(6);
+            assert_eq!((game.width(), game.height()), (width, height));
+        }
+    }
+
+    #[test]
+    fn test_narrow_board_clears_rows() {
+        let options = GameOptions {
+            width: 4,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 0);
+        game.active_piece = Some(ActivePiece::spawn(TetrominoKind::I, RotationState::Zero, 4));
+        game.step(Input::HardDrop);
+
+        assert_eq!(game.rows_cleared, 1);
+        assert!(game.game_space.iter().all(|row| row.iter().all(|cell| cell.is_none())));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_board_too_small() {
+        let options = GameOptions {
+            width: 3,
+            ..GameOptions::default()
+        };
+        Game::with_options(options, 0);
+    }
+
+    #[test]
+    fn test_same_seed_plays_the_same_game() {
+        let mut game1 = Game::with_seed(7);
+        let mut game2 = Game::with_seed(7);
+
+        for _ in 0..20 {
+            game1.step(Input::HardDrop);
+            game2.step(Input::HardDrop);
+        }
+
+        assert_eq!(game1.seed(), 7);
+        assert_eq!(game1.game_space, game2.game_space);
+        assert_eq!(game1.next_items, game2.next_items);
+
+        game1.restart_with_seed(7);
+        assert_eq!(game1.active_piece, Game::with_seed(7).active_piece);
+    }
+
+    #[test]
+    fn test_preview_follows_the_piece_sequence() {
+        let mut game = Game::with_seed(3);
+        let mut sequence = RandomizerKind::SevenBag.create(3);
+        let expected: Vec<TetrominoKind> = (0..30).map(|_| sequence.next_kind()).collect();
+
+        // Spans several bag refills.
+        for spawned in 0..20 {
+            let preview: Vec<TetrominoKind> =
+                game.preview(MAX_PREVIEW_COUNT).map(|item| item.kind).collect();
+
+            assert_eq!(game.active_piece.unwrap().kind, expected[spawned]);
+            assert_eq!(preview, expected[spawned + 1..spawned + 1 + MAX_PREVIEW_COUNT]);
+            assert_eq!(game.preview(3).count(), 3);
+
+            game.step(Input::HardDrop);
+            game.game_space = empty_board(BOARD_WIDTH, BUFFER_HEIGHT + BOARD_HEIGHT);
+        }
+    }
+
+    #[test]
+    fn test_hard_drop_locks_piece_and_scores() {
+        let mut game = Game::new();
+        let piece = game.active_piece.unwrap();
+        let distance = drop_distance(&game.game_space, &piece);
+
+        game.step(Input::HardDrop);
+
+        let locked = game.game_space.iter().flatten().filter(|cell| cell.is_some()).count();
+        assert_eq!(locked, 4);
+        assert_eq!(game.score, distance as u32 * 2);
+        assert!(game.active_piece.is_some());
+    }
+
+    #[test]
+    fn test_hold_swaps_once_per_drop() {
+        let mut game = Game::new();
+        let first_kind = game.active_piece.unwrap().kind;
+        let next_kind = game.next_items[0].kind;
+
+        game.step(Input::Hold);
+        assert_eq!(game.hold_item.as_ref().unwrap().kind, first_kind);
+        assert_eq!(game.active_piece.unwrap().kind, next_kind);
+
+        game.step(Input::Hold);
+        assert_eq!(game.active_piece.unwrap().kind, next_kind);
+
+        game.step(Input::HardDrop);
+        assert!(!game.hold_used);
+
+        game.step(Input::Hold);
+        assert_eq!(game.active_piece.unwrap().kind, first_kind);
+    }
+
+    #[test]
+    fn test_grounded_piece_waits_for_lock_delay() {
+        let mut game = Game::new();
+
+        while !game.is_grounded() {
+            game.step(Input::Gravity);
+        }
+
+        let piece = game.active_piece;
+        game.step(Input::Gravity);
+        game.step(Input::SoftDrop);
+        assert_eq!(game.active_piece, piece);
+
+        assert!(!game.advance(499));
+        assert!(game.advance(1));
+        assert_ne!(game.active_piece, piece);
+        assert_eq!(game.game_space.iter().flatten().filter(|cell| cell.is_some()).count(), 4);
+    }
+
+    #[test]
+    fn test_moves_on_the_ground_reset_lock_delay() {
+        let mut game = Game::new();
+        let floor = game.game_space.len() as isize - 2;
+        game.active_piece = Some(ActivePiece::new(TetrominoKind::O, RotationState::Zero, 0, floor));
+
+        assert!(!game.advance(400));
+        game.step(Input::Right);
+        assert!(!game.advance(400));
+        assert_eq!(game.lock_delay.resets(), 1);
+
+        for _ in 0..14 {
+            game.step(Input::Right);
+            game.step(Input::Left);
+        }
+
+        assert_eq!(game.lock_delay.resets(), 15);
+        assert!(game.advance(0));
+    }
+
+    #[test]
+    fn test_zero_lock_delay_locks_on_gravity() {
+        let mut game = Game::new();
+        game.lock_delay = LockDelay::new(0, 15);
+        let floor = game.game_space.len() - 1;
+        game.active_piece = Some(ActivePiece::new(TetrominoKind::O, RotationState::Zero, 0, floor as isize - 1));
+
+        game.step(Input::Gravity);
+
+        assert_eq!(game.game_space[floor][0], Some(Brick::new(TetrominoKind::O)));
+    }
+
+    #[test]
+    fn test_soft_drop_scores_one_point_per_row() {
+        let mut game = Game::new();
+
+        game.step(Input::SoftDrop);
+        game.step(Input::SoftDrop);
+        game.step(Input::Gravity);
+
+        assert_eq!(game.score, 2);
+    }
+
+    #[test]
+    fn test_t_spin_double_is_scored() {
+        let mut game = Game::new();
+        let brick = Some(Brick::new(TetrominoKind::O));
+
+        let floor = game.game_space.len() - 1;
+
+        game.game_space[floor - 2][3] = brick;
+        game.game_space[floor - 1] = vec![brick; BOARD_WIDTH];
+        game.game_space[floor] = vec![brick; BOARD_WIDTH];
+        game.game_space[floor - 1][3] = None;
+        game.game_space[floor - 1][4] = None;
+        game.game_space[floor - 1][5] = None;
+        game.game_space[floor][4] = None;
+        game.active_piece = Some(ActivePiece::new(TetrominoKind::T, RotationState::Left, 3, floor as isize - 2));
+
+        game.step(Input::RotateCounterclockwise);
+        game.step(Input::HardDrop);
+
+        let event = game.last_event.unwrap();
+        assert_eq!(event.spin, Spin::Full);
+        assert_eq!(event.lines, 2);
+        assert_eq!(game.score, 1200);
+        assert_eq!(game.rows_cleared, 2);
+    }
+
+    #[test]
+    fn test_sprint_ends_at_line_goal() {
+        let options = GameOptions {
+            width: 4,
+            mode: Mode::Sprint,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 0);
+        game.rows_cleared = 39;
+        game.advance(2500);
+        game.active_piece = Some(ActivePiece::spawn(TetrominoKind::I, RotationState::Zero, 4));
+
+        game.step(Input::HardDrop);
+
+        assert!(game.completed);
+        assert!(game.game_over);
+        assert_eq!(game.top_out, None);
+        assert_eq!(game.level, DEFAULT_LEVEL);
+        assert_eq!(game.line_times, vec![2500]);
+        assert_eq!(game.pieces_placed, 1);
+
+        game.advance(100);
+        assert_eq!(game.elapsed_ms, 2500);
+    }
+
+    #[test]
+    fn test_marathon_start_level_and_goal() {
+        let options = GameOptions {
+            width: 4,
+            marathon_goal: MarathonGoal::Lines150,
+            start_level: 12,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 0);
+        assert_eq!(game.level, 12);
+        assert_eq!(game.tick_rate_ms(), get_speed_by_level(12));
+
+        let clear_line = |game: &mut Game| {
+            game.active_piece = Some(ActivePiece::spawn(TetrominoKind::I, RotationState::Zero, 4));
+            game.step(Input::HardDrop);
+        };
+
+        // The first level up takes 100 lines from level 12.
+        game.rows_cleared = 98;
+        clear_line(&mut game);
+        assert_eq!(game.level, 12);
+        clear_line(&mut game);
+        assert_eq!(game.level, 13);
+
+        game.rows_cleared = 149;
+        clear_line(&mut game);
+        assert!(game.completed);
+        assert!(game.game_over);
+        assert_eq!(game.level, 18);
+    }
+
+    #[test]
+    fn test_endless_marathon_has_no_goal() {
+        let mut game = Game::with_options(GameOptions { width: 4, ..GameOptions::default() }, 0);
+        game.rows_cleared = 500;
+        game.active_piece = Some(ActivePiece::spawn(TetrominoKind::I, RotationState::Zero, 4));
+        game.step(Input::HardDrop);
+
+        assert!(!game.game_over);
+        assert_eq!(game.level, 50);
+    }
+
+    #[test]
+    fn test_dig_starts_with_garbage_and_ends_when_dug_out() {
+        let options = GameOptions {
+            width: 4,
+            height: 8,
+            mode: Mode::Dig,
+            dig_rows: 3,
+            messiness: 0,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 2);
+        let floor = game.game_space.len() - 1;
+
+        assert_eq!(game.garbage_remaining(), 3);
+        assert_eq!(game.game_space, Game::with_options(options, 2).game_space);
+
+        // A clean dig has all holes in one column; a vertical I clears three rows at once.
+        let hole = game.game_space[floor].iter().position(|cell| cell.is_none()).unwrap();
+        assert!((floor - 2..=floor).all(|row| game.game_space[row][hole].is_none()));
+
+        game.active_piece = Some(ActivePiece::new(TetrominoKind::I, RotationState::Right, hole as isize - 2, 0));
+        game.step(Input::HardDrop);
+
+        assert_eq!(game.rows_cleared, 3);
+        assert_eq!(game.garbage_remaining(), 0);
+        assert!(game.completed);
+        assert!(game.game_over);
+    }
+
+    #[test]
+    fn test_dig_rows_leave_the_top_row_free() {
+        let options = GameOptions {
+            height: 6,
+            mode: Mode::Dig,
+            dig_rows: 50,
+            ..GameOptions::default()
+        };
+
+        assert_eq!(Game::with_options(options, 0).garbage_remaining(), 5);
+    }
+
+    fn survival() -> Game {
+        let options = GameOptions {
+            mode: Mode::Survival,
+            ..GameOptions::default()
+        };
+
+        Game::with_options(options, 5)
+    }
+
+    #[test]
+    fn test_survival_garbage_rises_faster_with_level() {
+        let mut game = survival();
+        game.lock_delay = LockDelay::new(60_000, MAX_LOCK_RESETS);
+
+        game.advance(9_999);
+        assert_eq!(game.garbage_remaining(), 0);
+        assert_eq!(game.next_garbage_ms(), Some(1));
+
+        game.advance(1);
+        assert_eq!(game.garbage_remaining(), 1);
+
+        game.level = 4;
+        game.advance(8_000);
+        assert_eq!(game.garbage_remaining(), 2);
+        assert!(!game.game_over);
+    }
+
+    #[test]
+    fn test_rising_garbage_pushes_the_active_piece_up() {
+        let mut game = survival();
+        game.lock_delay = LockDelay::new(60_000, MAX_LOCK_RESETS);
+
+        let piece = game.active_piece.unwrap();
+        let grounded = piece.shifted(0, drop_distance(&game.game_space, &piece) as isize);
+        game.active_piece = Some(grounded);
+
+        game.advance(10_000);
+
+        assert_eq!(game.active_piece, Some(grounded.shifted(0, -1)));
+        assert!(game.is_grounded());
+
+        // A piece above the stack stays where it is.
+        game.active_piece = Some(piece);
+        game.advance(10_000);
+        assert_eq!(game.active_piece, Some(piece));
+    }
+
+    #[test]
+    fn test_garbage_pushing_bricks_off_the_top_ends_game() {
+        let mut game = survival();
+        game.game_space[0][0] = Some(Brick::new(TetrominoKind::O));
+
+        game.advance(10_000);
+
+        assert!(game.game_over);
+        assert_eq!(game.top_out, Some(TopOut::PushOut));
+        assert!(!game.completed);
+    }
+
+    fn master(width: usize) -> Game {
+        let options = GameOptions {
+            width,
+            mode: Mode::Master,
+            ..GameOptions::default()
+        };
+
+        Game::with_options(options, 3)
+    }
+
+    #[test]
+    fn test_master_gravity_is_counted_in_g() {
+        let mut game = master(10);
+        let piece = game.active_piece.unwrap();
+
+        // 4/256 G moves a row every 64 frames.
+        game.advance(1066);
+        assert_eq!(game.active_piece, Some(piece));
+
+        game.advance(1);
+        assert_eq!(game.active_piece, Some(piece.shifted(0, 1)));
+    }
+
+    #[test]
+    fn test_master_counts_pieces_and_waits_for_the_next_one() {
+        let mut game = master(10);
+        game.step(Input::HardDrop);
+
+        assert_eq!(game.level, 1);
+        assert_eq!(game.active_piece, None);
+        assert_eq!(game.entry_delay_ms, Some(416));
+
+        // Inputs during the entry delay do nothing.
+        game.step(Input::HardDrop);
+        assert_eq!(game.pieces_placed, 1);
+
+        game.advance(415);
+        assert_eq!(game.active_piece, None);
+
+        game.advance(1);
+        assert!(game.active_piece.is_some());
+        assert!(!game.game_over);
+    }
+
+    #[test]
+    fn test_master_pieces_land_at_once_at_twenty_g() {
+        let mut game = master(10);
+        game.level = 500;
+        game.step(Input::HardDrop);
+        game.advance(game.entry_delay_ms.unwrap());
+
+        assert!(game.is_grounded());
+        assert_eq!(game.lock_delay.delay_ms, 500);
+
+        // A piece moved off a ledge drops at once too.
+        game.step(Input::Left);
+        assert!(game.is_grounded());
+    }
+
+    #[test]
+    fn test_master_pieces_wait_for_lock_delay_at_twenty_g() {
+        let mut game = master(10);
+        game.level = 500;
+        game.step(Input::HardDrop);
+        game.advance(game.entry_delay_ms.unwrap());
+
+        let lock_delay_ms = game.lock_delay.delay_ms;
+        let piece = game.active_piece;
+
+        assert!(game.is_grounded());
+        assert!(!game.advance(lock_delay_ms - 1));
+        assert_eq!(game.active_piece, piece);
+        assert_eq!(game.pieces_placed, 1);
+
+        game.advance(1);
+        assert_eq!(game.pieces_placed, 2);
+    }
+
+    #[test]
+    fn test_master_ends_at_level_999() {
+        let mut game = master(4);
+        game.level = 998;
+        game.active_piece = Some(ActivePiece::spawn(TetrominoKind::I, RotationState::Zero, 4));
+        game.step(Input::HardDrop);
+
+        assert_eq!(game.level, MASTER_MAX_LEVEL);
+        assert!(game.completed);
+        assert!(game.game_over);
+    }
+
+    #[test]
+    fn test_ultra_ends_when_time_runs_out() {
+        let options = GameOptions {
+            mode: Mode::Ultra,
+            ultra_minutes: 2,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 0);
+
+        assert!(!game.advance(119_000));
+        assert!(!game.game_over);
+
+        game.advance(5_000);
+
+        assert!(game.game_over);
+        assert!(game.completed);
+        assert_eq!(game.elapsed_ms, 120_000);
+        assert!(game.active_piece.is_none());
+    }
+
+    #[test]
+    fn test_zen_plays_on_after_topping_out() {
+        let options = GameOptions {
+            mode: Mode::Zen,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 0);
+
+        for _ in 0..100 {
+            game.step(Input::HardDrop);
+        }
+
+        assert!(!game.game_over);
+        assert_eq!(game.top_out, None);
+        assert_eq!(game.level, DEFAULT_LEVEL);
+        assert!(game.score > 0);
+    }
+
+    #[test]
+    fn test_zen_gravity_is_optional() {
+        let options = GameOptions {
+            mode: Mode::Zen,
+            gravity: false,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 0);
+        let piece = game.active_piece;
+
+        game.step(Input::Gravity);
+        assert_eq!(game.active_piece, piece);
+
+        game.step(Input::SoftDrop);
+        assert_ne!(game.active_piece, piece);
+    }
+
+    fn puzzle(pieces: &str) -> Puzzle {
+        format!("tetris-puzzle 1\nname Test\ngoal lines 1\npieces {}\nboard\nGGG....GGG\n", pieces)
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_puzzle_is_solved_with_its_pieces() {
+        let mut game = Game::from_puzzle(puzzle("OI"));
+
+        assert_eq!(game.mode(), Mode::Puzzle);
+        assert_eq!(game.game_space[BUFFER_HEIGHT + BOARD_HEIGHT - 1][0], Some(Brick::garbage()));
+        assert_eq!(game.active_piece.unwrap().kind, TetrominoKind::O);
+        assert_eq!(game.pieces_left(), Some(1));
+
+        // The O goes on hold for the I, which completes the row.
+        game.step(Input::Hold);
+        assert_eq!(game.active_piece.unwrap().kind, TetrominoKind::I);
+        assert_eq!(game.pieces_left(), Some(0));
+
+        game.step(Input::HardDrop);
+        assert!(game.game_over);
+        assert!(game.completed);
+    }
+
+    #[test]
+    fn test_puzzle_fails_when_out_of_pieces() {
+        let mut game = Game::from_puzzle(puzzle("O"));
+
+        // Nothing is left to swap the last piece for.
+        game.step(Input::Hold);
+        assert_eq!(game.hold_item, None);
+
+        game.step(Input::HardDrop);
+        assert!(game.game_over);
+        assert!(!game.completed);
+        assert_eq!(game.top_out, None);
+
+        game.restart_with_seed(1);
+        assert!(!game.game_over);
+        assert_eq!(game.pieces_left(), Some(0));
+        assert_eq!(game.game_space[BUFFER_HEIGHT + BOARD_HEIGHT - 1][0], Some(Brick::garbage()));
+    }
+
+    #[test]
+    fn test_puzzle_starts_from_its_board() {
+        let puzzle = puzzle("IOT");
+        let game = Game::from_puzzle(puzzle.clone());
+
+        assert_eq!(game.game_space[BUFFER_HEIGHT..], puzzle.board[..]);
+        assert_eq!(game.active_piece.unwrap().kind, TetrominoKind::I);
+        assert_eq!(game.hold_item, None);
+        assert_eq!(game.garbage_drawn, 0);
+        assert_eq!(game.puzzle, Some(puzzle));
+    }
+
+    #[test]
+    fn test_puzzle_preview_stops_at_the_last_piece() {
+        let game = Game::from_puzzle(puzzle("IOT"));
+        let kinds: Vec<_> = game.preview(MAX_PREVIEW_COUNT).map(|item| item.kind).collect();
+
+        assert_eq!(kinds, vec![TetrominoKind::O, TetrominoKind::T]);
+    }
+
+    #[test]
+    fn test_ultra_clears_the_board_on_top_out() {
+        let options = GameOptions {
+            mode: Mode::Ultra,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 0);
+
+        for _ in 0..100 {
+            game.step(Input::HardDrop);
+        }
+
+        assert!(!game.game_over);
+        assert_eq!(game.top_out, None);
+        assert!(game.active_piece.is_some());
+        assert!(game.score > 0);
+    }
+
+    #[test]
+    fn test_finesse_faults() {
+        let mut game = Game::with_seed(1);
+        let spawn = ActivePiece::spawn(TetrominoKind::T, RotationState::Zero, BOARD_WIDTH);
+
+        // Holding left to the wall is a single input.
+        game.active_piece = Some(spawn);
+        for _ in 0..3 {
+            game.step(Input::Left);
+        }
+        game.step(Input::HardDrop);
+        assert_eq!(game.finesse_faults, 0);
+
+        // Left, right, left for a spot one shift away wastes two.
+        game.active_piece = Some(spawn);
+        game.step(Input::Left);
+        game.step(Input::Right);
+        game.step(Input::Left);
+        game.step(Input::HardDrop);
+        assert_eq!(game.finesse_faults, 2);
+        assert_eq!(game.pieces_placed, 2);
+    }
+
+    #[test]
+    fn test_piece_locked_in_buffer_ends_game() {
+        let mut game = Game::new();
+        let brick = Some(Brick::new(TetrominoKind::O));
+
+        // A column reaching into the buffer, with the spawn area itself left free.
+        for row in BUFFER_HEIGHT - 2..game.game_space.len() {
+            game.game_space[row][0] = brick;
+        }
+        game.active_piece = Some(ActivePiece::new(TetrominoKind::O, RotationState::Zero, 0, 0));
+
+        game.step(Input::HardDrop);
+
+        assert!(game.game_over);
+        assert_eq!(game.top_out, Some(TopOut::LockOut));
+        assert!(game.active_piece.is_none());
+    }
+
+    #[test]
+    fn test_piece_partly_visible_does_not_lock_out() {
+        let mut game = Game::new();
+        let brick = Some(Brick::new(TetrominoKind::O));
+
+        for row in BUFFER_HEIGHT + 1..game.game_space.len() {
+            game.game_space[row][0] = brick;
+        }
+        game.active_piece = Some(ActivePiece::new(TetrominoKind::O, RotationState::Zero, 0, 0));
+
+        game.step(Input::HardDrop);
+
+        assert!(!game.game_over);
+        assert!(game.active_piece.is_some());
+    }
+
+    #[test]
+    fn test_blocked_spawn_ends_game() {
+        let mut game = Game::new();
+        let brick = Some(Brick::new(TetrominoKind::O));
+
+        for row in game.game_space.iter_mut() {
+            *row = vec![brick; BOARD_WIDTH];
+            row[0] = None;
+            row[BOARD_WIDTH - 1] = None;
+        }
+        game.active_piece = Some(ActivePiece::new(TetrominoKind::I, RotationState::Left, -1, 0));
+
+        game.step(Input::HardDrop);
+
+        assert_eq!(game.rows_cleared, 0);
+        assert!(game.game_over);
+        assert_eq!(game.top_out, Some(TopOut::BlockOut));
+        assert!(game.active_piece.is_none());
+
+        let score = game.score;
+        game.step(Input::HardDrop);
+        assert_eq!(game.score, score);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::constants::MAX_MESSINESS;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Picks the hole of each garbage row. The holes come from their own stream of the game's
+/// seed, so the same seed always digs through the same garbage.
+#[derive(Debug, Clone)]
+pub struct GarbageHoles {
+    rng: ChaCha8Rng,
+    /// Chance in percent that a hole is not under the one above it: 0 digs a single straight
+    /// well, 100 moves every hole.
+    messiness: u32,
+    last_hole: Option<usize>,
+}
+
+impl GarbageHoles {
+    pub fn new(seed: u64, messiness: u32) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_stream(1);
+
+        Self {
+            rng,
+            messiness,
+            last_hole: None,
+        }
+    }
+
+    /// Column of the hole in the next row of a board `cols` wide.
+    pub fn next_hole(&mut self, cols: usize) -> usize {
+        let hole = match self.last_hole {
+            Some(last) if last < cols && self.rng.random_range(0..MAX_MESSINESS) >= self.messiness => last,
+            Some(last) if last < cols => (last + self.rng.random_range(1..cols)) % cols,
+            _ => self.rng.random_range(0..cols),
+        };
+
+        self.last_hole = Some(hole);
+        hole
+    }
+
+    /// Holes of the next `count` rows, from the top one down.
+    pub fn next_holes(&mut self, count: usize, cols: usize) -> Vec<usize> {
+        (0..count).map(|_| self.next_hole(cols)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_holes() {
+        let holes = GarbageHoles::new(3, 50).next_holes(30, 10);
+
+        assert_eq!(GarbageHoles::new(3, 50).next_holes(30, 10), holes);
+        assert!(holes.iter().all(|&hole| hole < 10));
+    }
+
+    #[test]
+    fn test_messiness() {
+        let clean = GarbageHoles::new(8, 0).next_holes(30, 10);
+        assert!(clean.iter().all(|&hole| hole == clean[0]));
+
+        let messy = GarbageHoles::new(8, 100).next_holes(30, 10);
+        assert!(messy.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+}
@@ -2,6 +2,25 @@ pub fn get_level(rows: u32) -> u32 {
     rows / 10
 }
 
+/// Level after clearing `rows` in a game started at `start_level`. As on the NES, the first
+/// level up takes `min(start * 10 + 10, max(100, start * 10 - 50))` lines and every later
+/// one 10 more, so high starting levels are not left behind right away.
+pub fn get_level_from(start_level: u32, rows: u32) -> u32 {
+    let first_level_up = (start_level * 10 + 10).min(100.max((start_level * 10).saturating_sub(50)));
+
+    if rows < first_level_up {
+        start_level
+    } else {
+        start_level + 1 + (rows - first_level_up) / 10
+    }
+}
+
+/// Milliseconds between two garbage rows rising in survival: ten seconds at level 0, half a
+/// second less every level, down to one second.
+pub fn get_garbage_interval_by_level(level: u32) -> u64 {
+    10_000u64.saturating_sub(level as u64 * 500).max(1_000)
+}
+
 pub fn get_speed_by_level(level: u32) -> u64 {
     match level {
         0 => 800,
@@ -14,9 +33,9 @@ pub fn get_speed_by_level(level: u32) -> u64 {
         7 => 220,
         8 => 130,
         9 => 100,
-        10 | 11 | 12 => 80,
-        13 | 14 | 15 => 70,
-        16 | 17 | 18 => 50,
+        10..=12 => 80,
+        13..=15 => 70,
+        16..=18 => 50,
         19..=28 => 30,
         _ => 17,
     }
@@ -54,6 +73,32 @@ mod tests {
         assert_eq!(get_level(232), 23);
     }
 
+    #[test]
+    fn test_get_level_from_start_level_zero_matches_get_level() {
+        for rows in 0..300 {
+            assert_eq!(get_level_from(0, rows), get_level(rows), "{} rows", rows);
+        }
+    }
+
+    #[test]
+    fn test_get_level_from_first_level_up() {
+        // Low starting levels level up after start * 10 + 10 lines.
+        assert_eq!(get_level_from(5, 59), 5);
+        assert_eq!(get_level_from(5, 60), 6);
+        assert_eq!(get_level_from(5, 70), 7);
+
+        // From 10 to 15 the first level up is capped at 100 lines.
+        assert_eq!(get_level_from(9, 100), 10);
+        assert_eq!(get_level_from(12, 99), 12);
+        assert_eq!(get_level_from(12, 100), 13);
+
+        // From 16 on it takes start * 10 - 50 lines.
+        assert_eq!(get_level_from(18, 129), 18);
+        assert_eq!(get_level_from(18, 130), 19);
+        assert_eq!(get_level_from(19, 140), 20);
+        assert_eq!(get_level_from(19, 150), 21);
+    }
+
     #[test]
     fn test_get_speed_lower_levels() {
         // Test specific levels at lower values
@@ -94,4 +139,12 @@ mod tests {
         assert_eq!(get_speed_by_level(29), 17);
         assert_eq!(get_speed_by_level(100), 17);
     }
+
+    #[test]
+    fn test_garbage_interval_shrinks_with_level() {
+        assert_eq!(get_garbage_interval_by_level(0), 10_000);
+        assert_eq!(get_garbage_interval_by_level(4), 8_000);
+        assert_eq!(get_garbage_interval_by_level(18), 1_000);
+        assert_eq!(get_garbage_interval_by_level(29), 1_000);
+    }
 }
\ No newline at end of file
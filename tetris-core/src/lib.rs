@@ -0,0 +1,31 @@
+//! Game rules for Tetris without any rendering or windowing: the board, pieces, movement,
+//! SRS rotation, randomizers, scoring, level progression, garbage, game modes, master gravity,
+//! puzzles, finesse and saved games.
+//! Drive a [`Game`] with [`Game::step`].
+
+pub mod bag;
+pub mod board;
+pub mod constants;
+pub mod enums;
+pub mod finesse;
+pub mod game;
+pub mod garbage;
+pub mod level;
+pub mod lock_delay;
+pub mod master;
+pub mod mode;
+pub mod moves;
+pub mod piece;
+pub mod puzzle;
+pub mod randomizer;
+pub mod rotations;
+pub mod save;
+pub mod scoring;
+pub mod tetromino;
+pub mod types;
+
+pub use game::{Game, GameOptions, Input, TopOut};
+pub use lock_delay::LockDelay;
+pub use mode::Mode;
+pub use randomizer::{Randomizer, RandomizerKind};
+pub use save::LoadError;
@@ -0,0 +1,155 @@
+use crate::constants::{DEFAULT_LOCK_DELAY_MS, MAX_LOCK_RESETS};
+
+/// Guideline "extended placement" lock delay. A grounded piece locks once `delay_ms` passes
+/// without a successful move or rotation. Each such move restarts the timer, at most
+/// `max_resets` times; reaching a new lowest row gives the piece a fresh set of resets. With
+/// `max_resets` at 0 moves never restart the timer, as in master, but the piece still gets the
+/// full delay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockDelay {
+    pub delay_ms: u64,
+    pub max_resets: u32,
+    elapsed_ms: u64,
+    resets: u32,
+    lowest_row: isize,
+}
+
+impl Default for LockDelay {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCK_DELAY_MS, MAX_LOCK_RESETS)
+    }
+}
+
+impl LockDelay {
+    pub fn new(delay_ms: u64, max_resets: u32) -> Self {
+        Self {
+            delay_ms,
+            max_resets,
+            elapsed_ms: 0,
+            resets: 0,
+            lowest_row: isize::MIN,
+        }
+    }
+
+    /// Forgets everything about the previous piece.
+    pub fn start(&mut self, row: isize) {
+        self.elapsed_ms = 0;
+        self.resets = 0;
+        self.lowest_row = row;
+    }
+
+    /// Called after the piece moved down to `row`.
+    pub fn fell(&mut self, row: isize) {
+        if row > self.lowest_row {
+            self.start(row);
+        }
+    }
+
+    /// Called after a successful shift or rotation.
+    pub fn moved(&mut self, grounded: bool) {
+        if grounded && self.resets < self.max_resets {
+            self.elapsed_ms = 0;
+            self.resets += 1;
+        }
+    }
+
+    /// Advances the timer and reports whether the piece has to lock now.
+    pub fn advance(&mut self, elapsed_ms: u64, grounded: bool) -> bool {
+        if !grounded {
+            self.elapsed_ms = 0;
+            return false;
+        }
+
+        if self.max_resets > 0 && self.resets >= self.max_resets {
+            return true;
+        }
+
+        self.elapsed_ms += elapsed_ms;
+        self.elapsed_ms >= self.delay_ms
+    }
+
+    pub fn resets(&self) -> u32 {
+        self.resets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locks_after_delay() {
+        let mut lock_delay = LockDelay::new(500, 15);
+        lock_delay.start(0);
+
+        assert!(!lock_delay.advance(300, true));
+        assert!(!lock_delay.advance(199, true));
+        assert!(lock_delay.advance(1, true));
+    }
+
+    #[test]
+    fn test_timer_does_not_run_in_the_air() {
+        let mut lock_delay = LockDelay::new(500, 15);
+        lock_delay.start(0);
+
+        assert!(!lock_delay.advance(400, true));
+        assert!(!lock_delay.advance(400, false));
+        assert!(!lock_delay.advance(400, true));
+    }
+
+    #[test]
+    fn test_move_resets_timer() {
+        let mut lock_delay = LockDelay::new(500, 15);
+        lock_delay.start(0);
+
+        assert!(!lock_delay.advance(400, true));
+        lock_delay.moved(true);
+        assert!(!lock_delay.advance(400, true));
+        assert_eq!(lock_delay.resets(), 1);
+
+        lock_delay.moved(false);
+        assert_eq!(lock_delay.resets(), 1);
+    }
+
+    #[test]
+    fn test_reset_limit_locks_on_touch_down() {
+        let mut lock_delay = LockDelay::new(500, 15);
+        lock_delay.start(0);
+
+        for _ in 0..15 {
+            lock_delay.moved(true);
+        }
+
+        assert_eq!(lock_delay.resets(), 15);
+        assert!(!lock_delay.advance(16, false));
+        assert!(lock_delay.advance(0, true));
+    }
+
+    #[test]
+    fn test_no_move_resets_still_waits_for_delay() {
+        let mut lock_delay = LockDelay::new(500, 0);
+        lock_delay.start(0);
+
+        lock_delay.moved(true);
+        assert_eq!(lock_delay.resets(), 0);
+        assert!(!lock_delay.advance(499, true));
+        assert!(lock_delay.advance(1, true));
+    }
+
+    #[test]
+    fn test_new_lowest_row_restores_resets() {
+        let mut lock_delay = LockDelay::new(500, 15);
+        lock_delay.start(3);
+
+        for _ in 0..15 {
+            lock_delay.moved(true);
+        }
+
+        lock_delay.fell(3);
+        assert_eq!(lock_delay.resets(), 15);
+
+        lock_delay.fell(4);
+        assert_eq!(lock_delay.resets(), 0);
+        assert!(!lock_delay.advance(16, true));
+    }
+}
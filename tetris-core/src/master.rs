@@ -0,0 +1,157 @@
+use crate::constants::{FRAMES_PER_SECOND, MASTER_MAX_LEVEL, MASTER_SECTION_LEVELS};
+
+/// TGM's gravity curve as the level each step starts at and its gravity in 1/256 G: a slow
+/// build up, a drop back at 200, 1G at 251 and 20G from 500.
+const GRAVITY_CURVE: [(u32, u32); 30] = [
+    (0, 4),
+    (30, 6),
+    (35, 8),
+    (40, 10),
+    (50, 12),
+    (60, 16),
+    (70, 32),
+    (80, 48),
+    (90, 64),
+    (100, 80),
+    (120, 96),
+    (140, 112),
+    (160, 128),
+    (170, 144),
+    (200, 4),
+    (220, 32),
+    (230, 64),
+    (233, 96),
+    (236, 128),
+    (239, 160),
+    (243, 192),
+    (247, 224),
+    (251, 256),
+    (300, 512),
+    (330, 768),
+    (360, 1024),
+    (400, 1280),
+    (420, 1024),
+    (450, 768),
+    (500, 5120),
+];
+
+/// Delays of a master section, in frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delays {
+    /// Entry delay (ARE) between a piece locking and the next one appearing.
+    pub are: u32,
+    /// Entry delay after a lock that cleared lines, on top of `line_clear`.
+    pub line_are: u32,
+    /// Time the cleared lines take to disappear.
+    pub line_clear: u32,
+    pub lock: u32,
+}
+
+impl Delays {
+    /// Milliseconds until the next piece appears after a lock that cleared `cleared_rows`.
+    pub fn entry_delay_ms(&self, cleared_rows: u32) -> u64 {
+        match cleared_rows {
+            0 => frames_to_ms(self.are),
+            _ => frames_to_ms(self.line_clear + self.line_are),
+        }
+    }
+
+    pub fn lock_delay_ms(&self) -> u64 {
+        frames_to_ms(self.lock)
+    }
+}
+
+pub fn frames_to_ms(frames: u32) -> u64 {
+    frames as u64 * 1000 / FRAMES_PER_SECOND
+}
+
+/// Gravity at `level` in 1/`GRAVITY_UNIT` rows per frame.
+pub fn get_gravity(level: u32) -> u32 {
+    GRAVITY_CURVE
+        .iter()
+        .rev()
+        .find(|(from, _)| level >= *from)
+        .map_or(GRAVITY_CURVE[0].1, |&(_, gravity)| gravity)
+}
+
+/// The delays of `level`'s section. Once gravity is maxed out at 500, every section shortens
+/// them further, as in TGM2's master mode.
+pub fn get_delays(level: u32) -> Delays {
+    let (are, line_are, line_clear, lock) = match level / MASTER_SECTION_LEVELS {
+        0..=4 => (25, 25, 40, 30),
+        5 => (25, 25, 25, 30),
+        6 => (25, 16, 16, 30),
+        7 => (16, 12, 12, 30),
+        8 => (12, 6, 6, 30),
+        _ => (12, 6, 6, 17),
+    };
+
+    Delays {
+        are,
+        line_are,
+        line_clear,
+        lock,
+    }
+}
+
+/// Level after a piece locked at `level` and cleared `cleared_rows`. Every cleared line counts,
+/// and so does the piece itself unless the level stands at the end of a section or at 998,
+/// which only a line clear gets past.
+pub fn get_next_level(level: u32, cleared_rows: u32) -> u32 {
+    let level = (level + cleared_rows).min(MASTER_MAX_LEVEL);
+    let section_stop = level % MASTER_SECTION_LEVELS == MASTER_SECTION_LEVELS - 1 || level >= MASTER_MAX_LEVEL - 1;
+
+    if section_stop { level } else { level + 1 }
+}
+
+/// The level the current section stops at.
+pub fn section_stop(level: u32) -> u32 {
+    (level / MASTER_SECTION_LEVELS * MASTER_SECTION_LEVELS + MASTER_SECTION_LEVELS - 1).min(MASTER_MAX_LEVEL - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{GRAVITY_UNIT, TWENTY_G};
+
+    #[test]
+    fn test_gravity_curve() {
+        assert_eq!(get_gravity(0), 4);
+        assert_eq!(get_gravity(34), 6);
+        assert_eq!(get_gravity(199), 144);
+        assert_eq!(get_gravity(200), 4);
+        assert_eq!(get_gravity(251), GRAVITY_UNIT);
+        assert_eq!(get_gravity(499), 768);
+        assert_eq!(get_gravity(500), TWENTY_G);
+        assert_eq!(get_gravity(999), TWENTY_G);
+    }
+
+    #[test]
+    fn test_pieces_stop_at_the_end_of_a_section() {
+        assert_eq!(get_next_level(0, 0), 1);
+        assert_eq!(get_next_level(98, 0), 99);
+        assert_eq!(get_next_level(99, 0), 99);
+        assert_eq!(get_next_level(99, 1), 101);
+        assert_eq!(get_next_level(97, 4), 102);
+        assert_eq!(get_next_level(997, 0), 998);
+        assert_eq!(get_next_level(998, 0), 998);
+        assert_eq!(get_next_level(998, 3), 999);
+    }
+
+    #[test]
+    fn test_section_stop() {
+        assert_eq!(section_stop(0), 99);
+        assert_eq!(section_stop(99), 99);
+        assert_eq!(section_stop(100), 199);
+        assert_eq!(section_stop(950), 998);
+    }
+
+    #[test]
+    fn test_delays_shorten_after_twenty_g() {
+        assert_eq!(get_delays(0), get_delays(499));
+        assert_eq!(get_delays(0).entry_delay_ms(0), 416);
+        assert_eq!(get_delays(0).entry_delay_ms(2), 1083);
+        assert!(get_delays(950).entry_delay_ms(1) < get_delays(550).entry_delay_ms(1));
+        assert_eq!(get_delays(999).lock_delay_ms(), 283);
+    }
+}
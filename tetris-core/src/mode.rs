@@ -0,0 +1,159 @@
+use std::str::FromStr;
+
+/// What a game is played for and when it ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Speeding up every 10 lines until the goal of `MarathonGoal` is reached or the stack
+    /// tops out.
+    #[default]
+    Marathon,
+    /// Clear `constants::SPRINT_LINES` lines as fast as possible at a constant speed.
+    Sprint,
+    /// Score as much as possible before the clock runs out. Topping out clears the board
+    /// instead of ending the game.
+    Ultra,
+    /// Clear every garbage row the board starts with as fast as possible at a constant speed.
+    Dig,
+    /// Hold out as long as possible while garbage rows rise from the bottom, faster with every
+    /// level.
+    Survival,
+    /// Race TGM style to level 999 under gravity counted in G, up to 20G, with entry and line
+    /// clear delays that shorten section by section.
+    Master,
+    /// Relaxed play at a constant speed, gravity optional. Topping out clears the board and the
+    /// score keeps adding up.
+    Zen,
+    /// Reach the goal of a `Puzzle` from its board with its pieces, in their order.
+    Puzzle,
+}
+
+impl Mode {
+    pub const ALL: [Mode; 8] = [
+        Mode::Marathon,
+        Mode::Sprint,
+        Mode::Ultra,
+        Mode::Dig,
+        Mode::Survival,
+        Mode::Master,
+        Mode::Zen,
+        Mode::Puzzle,
+    ];
+
+    /// Name used on the command line and in saved files.
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Marathon => "marathon",
+            Mode::Sprint => "sprint",
+            Mode::Ultra => "ultra",
+            Mode::Dig => "dig",
+            Mode::Survival => "survival",
+            Mode::Master => "master",
+            Mode::Zen => "zen",
+            Mode::Puzzle => "puzzle",
+        }
+    }
+
+    /// Whether cleared lines raise the level and with it the speed. Master counts its levels
+    /// its own way.
+    pub fn levels_up(self) -> bool {
+        match self {
+            Mode::Marathon | Mode::Ultra | Mode::Survival => true,
+            Mode::Sprint | Mode::Dig | Mode::Master | Mode::Zen | Mode::Puzzle => false,
+        }
+    }
+
+    /// Whether a block out or lock out ends the game.
+    pub fn tops_out(self) -> bool {
+        match self {
+            Mode::Marathon | Mode::Sprint | Mode::Dig | Mode::Survival | Mode::Master | Mode::Puzzle => true,
+            Mode::Ultra | Mode::Zen => false,
+        }
+    }
+
+    /// Whether the game races a clock, which the front end then redraws every frame.
+    pub fn is_timed(self) -> bool {
+        match self {
+            Mode::Marathon | Mode::Zen | Mode::Puzzle => false,
+            Mode::Sprint | Mode::Ultra | Mode::Dig | Mode::Survival | Mode::Master => true,
+        }
+    }
+
+    /// Whether gravity is counted in G per frame by `Game::advance` instead of coming as
+    /// `Input::Gravity` steps.
+    pub fn has_frame_gravity(self) -> bool {
+        self == Mode::Master
+    }
+}
+
+/// Lines that win a marathon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarathonGoal {
+    /// 150 lines, the classic guideline marathon.
+    Lines150,
+    Lines200,
+    /// No goal; the game only ends by topping out.
+    #[default]
+    Endless,
+}
+
+impl MarathonGoal {
+    pub const ALL: [MarathonGoal; 3] = [MarathonGoal::Lines150, MarathonGoal::Lines200, MarathonGoal::Endless];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MarathonGoal::Lines150 => "150",
+            MarathonGoal::Lines200 => "200",
+            MarathonGoal::Endless => "endless",
+        }
+    }
+
+    pub fn lines(self) -> Option<u32> {
+        match self {
+            MarathonGoal::Lines150 => Some(150),
+            MarathonGoal::Lines200 => Some(200),
+            MarathonGoal::Endless => None,
+        }
+    }
+}
+
+impl FromStr for MarathonGoal {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        MarathonGoal::ALL
+            .into_iter()
+            .find(|goal| goal.name() == name)
+            .ok_or_else(|| format!("unknown goal '{}'", name))
+    }
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Mode::ALL
+            .into_iter()
+            .find(|mode| mode.name() == name)
+            .ok_or_else(|| format!("unknown mode '{}'", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_round_trip() {
+        for mode in Mode::ALL {
+            assert_eq!(mode.name().parse::<Mode>(), Ok(mode));
+        }
+
+        assert!("practice".parse::<Mode>().is_err());
+
+        for goal in MarathonGoal::ALL {
+            assert_eq!(goal.name().parse::<MarathonGoal>(), Ok(goal));
+        }
+
+        assert!("100".parse::<MarathonGoal>().is_err());
+    }
+}
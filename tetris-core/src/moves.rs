@@ -0,0 +1,322 @@
+use crate::constants::BUFFER_HEIGHT;
+use crate::piece::ActivePiece;
+use crate::types::Matrix;
+
+fn try_shift(game_space: &Matrix, piece: &mut ActivePiece, dx: isize, dy: isize) -> bool {
+    let moved = piece.shifted(dx, dy);
+
+    if !moved.fits(game_space) {
+        return false;
+    }
+
+    *piece = moved;
+    true
+}
+
+pub fn move_right(game_space: &Matrix, piece: &mut ActivePiece) -> bool {
+    try_shift(game_space, piece, 1, 0)
+}
+
+pub fn move_left(game_space: &Matrix, piece: &mut ActivePiece) -> bool {
+    try_shift(game_space, piece, -1, 0)
+}
+
+/// Moves the piece one row down. Returns false when it rests on the floor or the stack.
+pub fn move_bottom(game_space: &Matrix, piece: &mut ActivePiece) -> bool {
+    try_shift(game_space, piece, 0, 1)
+}
+
+/// Number of rows the piece can fall before `move_bottom` would refuse to move it.
+pub fn drop_distance(game_space: &Matrix, piece: &ActivePiece) -> usize {
+    let mut distance = 0;
+
+    while piece.shifted(0, distance as isize + 1).fits(game_space) {
+        distance += 1;
+    }
+
+    distance
+}
+
+fn is_on_board(game_space: &Matrix, row: isize) -> bool {
+    row >= 0 && (row as usize) < game_space.len()
+}
+
+/// Writes the piece's bricks into the board. Bricks outside the board are dropped.
+pub fn lock_piece(game_space: &mut Matrix, piece: &ActivePiece) {
+    for (row, col) in piece.cells() {
+        if is_on_board(game_space, row) {
+            game_space[row as usize][col as usize] = Some(piece.brick());
+        }
+    }
+}
+
+/// Block out: a newly spawned piece overlaps the stack.
+pub fn is_block_out(game_space: &Matrix, piece: &ActivePiece) -> bool {
+    !piece.fits(game_space)
+}
+
+/// Lock out: a piece locked with every brick in the hidden buffer zone.
+pub fn is_lock_out(piece: &ActivePiece) -> bool {
+    piece.cells().iter().all(|&(row, _)| row < BUFFER_HEIGHT as isize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{RotationState, TetrominoKind};
+    use crate::tetromino::{Brick, Tetromino};
+
+    fn spawn(kind: TetrominoKind, cols: usize) -> ActivePiece {
+        ActivePiece::spawn(kind, Tetromino::from_kind(kind).rotation, cols)
+    }
+
+    fn board(rows: usize) -> Matrix {
+        vec![vec![None; 10]; BUFFER_HEIGHT + rows]
+    }
+
+    #[test]
+    fn test_block_out() {
+        let non_moving_brick = Some(Brick::new(TetrominoKind::O));
+
+        for kind in TetrominoKind::ALL {
+            let piece = spawn(kind, 10);
+
+            for (row, col) in piece.cells() {
+                let mut game_space = board(4);
+                game_space[row as usize][col as usize] = non_moving_brick;
+
+                assert!(is_block_out(&game_space, &piece), "{:?} at {:?}", kind, (row, col));
+            }
+        }
+    }
+
+    #[test]
+    fn test_spawn_above_stack_is_not_block_out() {
+        let non_moving_brick = Some(Brick::new(TetrominoKind::O));
+        let mut game_space = board(4);
+        game_space[BUFFER_HEIGHT] = vec![non_moving_brick; 10];
+
+        for kind in TetrominoKind::ALL {
+            assert!(!is_block_out(&game_space, &spawn(kind, 10)), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_lock_out() {
+        let piece = spawn(TetrominoKind::O, 10);
+        assert!(is_lock_out(&piece));
+
+        assert!(!is_lock_out(&piece.shifted(0, 1)));
+        assert!(!is_lock_out(&piece.shifted(0, 2)));
+    }
+
+    #[test]
+    fn test_spawn_position() {
+        // Rows 21 and 22 counted from the floor are the last two buffer rows.
+        let row_21 = BUFFER_HEIGHT as isize - 1;
+        let row_22 = row_21 - 1;
+
+        let sorted_cells = |kind| {
+            let mut cells = spawn(kind, 10).cells().to_vec();
+            cells.sort();
+            cells
+        };
+
+        assert_eq!(sorted_cells(TetrominoKind::I), [(row_21, 3), (row_21, 4), (row_21, 5), (row_21, 6)]);
+        assert_eq!(sorted_cells(TetrominoKind::O), [(row_22, 4), (row_22, 5), (row_21, 4), (row_21, 5)]);
+        assert_eq!(sorted_cells(TetrominoKind::T), [(row_22, 4), (row_21, 3), (row_21, 4), (row_21, 5)]);
+        assert_eq!(sorted_cells(TetrominoKind::L), [(row_22, 5), (row_21, 3), (row_21, 4), (row_21, 5)]);
+        assert_eq!(sorted_cells(TetrominoKind::J), [(row_22, 3), (row_21, 3), (row_21, 4), (row_21, 5)]);
+        assert_eq!(sorted_cells(TetrominoKind::S), [(row_22, 4), (row_22, 5), (row_21, 3), (row_21, 4)]);
+        assert_eq!(sorted_cells(TetrominoKind::Z), [(row_22, 3), (row_22, 4), (row_21, 4), (row_21, 5)]);
+    }
+
+    #[test]
+    fn test_left_moving_piece() {
+        let game_space: Matrix = vec![vec![None; 10]; 4];
+        let mut piece = ActivePiece::new(TetrominoKind::J, RotationState::Zero, 1, 1);
+
+        assert!(move_left(&game_space, &mut piece));
+        assert_eq!(piece.x, 0);
+
+        assert!(!move_left(&game_space, &mut piece));
+        assert_eq!(piece.x, 0);
+    }
+
+    #[test]
+    fn test_left_collision_case() {
+        let non_moving_brick = Some(Brick::new(TetrominoKind::O));
+
+        let game_space: Matrix = vec![
+            vec![None; 10],
+            vec![
+                None,
+                non_moving_brick,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ],
+            vec![None; 10],
+            vec![None; 10],
+        ];
+        let mut piece = ActivePiece::new(TetrominoKind::J, RotationState::Zero, 2, 1);
+
+        assert!(!move_left(&game_space, &mut piece));
+        assert_eq!(piece.x, 2);
+    }
+
+    #[test]
+    fn test_right_moving_piece() {
+        let non_moving_brick = Some(Brick::new(TetrominoKind::O));
+
+        let game_space: Matrix = vec![
+            vec![None; 10],
+            vec![None; 10],
+            vec![None; 10],
+            vec![None; 10],
+            vec![
+                non_moving_brick,
+                non_moving_brick,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ],
+        ];
+        let mut piece = ActivePiece::new(TetrominoKind::J, RotationState::Zero, 1, 1);
+
+        assert!(move_right(&game_space, &mut piece));
+        assert_eq!(piece.x, 2);
+
+        assert!(move_right(&game_space, &mut piece));
+        assert_eq!(piece.x, 3);
+    }
+
+    #[test]
+    fn test_right_collision_case() {
+        let non_moving_brick = Some(Brick::new(TetrominoKind::O));
+
+        let game_space: Matrix = vec![
+            vec![None; 10],
+            vec![
+                None,
+                None,
+                None,
+                non_moving_brick,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ],
+            vec![None; 10],
+            vec![None; 10],
+        ];
+        let mut piece = ActivePiece::new(TetrominoKind::O, RotationState::Zero, 1, 1);
+
+        assert!(!move_right(&game_space, &mut piece));
+        assert_eq!(piece.x, 1);
+
+        let mut piece = ActivePiece::new(TetrominoKind::O, RotationState::Zero, 8, 2);
+
+        assert!(!move_right(&game_space, &mut piece));
+        assert_eq!(piece.x, 8);
+    }
+
+    #[test]
+    fn test_bottom_collision_case() {
+        let non_moving_brick = Some(Brick::new(TetrominoKind::O));
+
+        let game_space: Matrix = vec![
+            vec![None; 10],
+            vec![None; 10],
+            vec![None; 10],
+            vec![
+                None,
+                non_moving_brick,
+                non_moving_brick,
+                non_moving_brick,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ],
+            vec![None; 10],
+        ];
+        let mut piece = ActivePiece::new(TetrominoKind::O, RotationState::Zero, 2, 1);
+
+        assert!(!move_bottom(&game_space, &mut piece));
+        assert_eq!(piece.y, 1);
+    }
+
+    #[test]
+    fn test_bottom_moving_piece() {
+        let game_space: Matrix = vec![vec![None; 10]; 4];
+        let mut piece = ActivePiece::new(TetrominoKind::J, RotationState::Zero, 1, 1);
+
+        assert!(move_bottom(&game_space, &mut piece));
+        assert_eq!(piece.y, 2);
+
+        assert!(!move_bottom(&game_space, &mut piece));
+        assert_eq!(piece.y, 2);
+    }
+
+    #[test]
+    fn test_lock_piece() {
+        let mut game_space: Matrix = vec![vec![None; 5]; 3];
+        let piece = ActivePiece::new(TetrominoKind::J, RotationState::Zero, 1, 1);
+        let brick = Some(piece.brick());
+
+        lock_piece(&mut game_space, &piece);
+
+        let expected_game_space: Matrix = vec![
+            vec![None; 5],
+            vec![None, brick, None, None, None],
+            vec![None, brick, brick, brick, None],
+        ];
+
+        assert_eq!(game_space, expected_game_space);
+    }
+
+    #[test]
+    fn test_drop_distance_to_floor() {
+        let game_space: Matrix = vec![vec![None; 5]; 4];
+        let piece = ActivePiece::new(TetrominoKind::O, RotationState::Zero, 1, 0);
+
+        assert_eq!(drop_distance(&game_space, &piece), 2);
+    }
+
+    #[test]
+    fn test_drop_distance_onto_stack() {
+        let non_moving_brick = Some(Brick::new(TetrominoKind::O));
+
+        let game_space: Matrix = vec![
+            vec![None; 5],
+            vec![None; 5],
+            vec![None; 5],
+            vec![None; 5],
+            vec![None, None, None, non_moving_brick, None],
+            vec![non_moving_brick; 5],
+        ];
+        let mut piece = ActivePiece::new(TetrominoKind::T, RotationState::Two, 1, -1);
+
+        assert_eq!(drop_distance(&game_space, &piece), 3);
+
+        for _ in 0..3 {
+            assert!(move_bottom(&game_space, &mut piece));
+        }
+        assert!(!move_bottom(&game_space, &mut piece));
+    }
+}
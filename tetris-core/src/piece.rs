@@ -0,0 +1,70 @@
+use crate::constants::BUFFER_HEIGHT;
+use crate::enums::{RotationState, TetrominoKind};
+use crate::tetromino::{Brick, box_size, shape_cells};
+use crate::types::Matrix;
+
+/// The falling piece. `x` and `y` are the board column and row of the top-left corner of
+/// its bounding box, which may lie outside the board when the box has empty edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ActivePiece {
+    pub kind: TetrominoKind,
+    pub rotation: RotationState,
+    pub x: isize,
+    pub y: isize,
+}
+
+impl ActivePiece {
+    pub fn new(kind: TetrominoKind, rotation: RotationState, x: isize, y: isize) -> Self {
+        Self {
+            kind,
+            rotation,
+            x,
+            y,
+        }
+    }
+
+    /// Places the piece just above the visible board of a board `cols` wide, its lowest filled
+    /// row on the last buffer row. The bounding box is centered, rounding to the left, which on
+    /// a 10-wide board puts I in columns 3-6, O in 4-5 and the others in 3-5.
+    pub fn spawn(kind: TetrominoKind, rotation: RotationState, cols: usize) -> Self {
+        let bottom = shape_cells(kind, rotation)
+            .iter()
+            .map(|&(row, _)| row)
+            .max()
+            .unwrap_or(0);
+        let x = (cols - box_size(kind)) / 2;
+
+        Self::new(kind, rotation, x as isize, BUFFER_HEIGHT as isize - 1 - bottom as isize)
+    }
+
+    /// Board `(row, col)` of each of the four bricks.
+    pub fn cells(&self) -> [(isize, isize); 4] {
+        shape_cells(self.kind, self.rotation)
+            .map(|(row, col)| (self.y + row as isize, self.x + col as isize))
+    }
+
+    pub fn shifted(&self, dx: isize, dy: isize) -> Self {
+        Self {
+            x: self.x + dx,
+            y: self.y + dy,
+            ..*self
+        }
+    }
+
+    pub fn fits(&self, game_space: &Matrix) -> bool {
+        let rows = game_space.len() as isize;
+        let cols = game_space[0].len() as isize;
+
+        self.cells().iter().all(|&(row, col)| {
+            row >= 0
+                && col >= 0
+                && row < rows
+                && col < cols
+                && game_space[row as usize][col as usize].is_none()
+        })
+    }
+
+    pub fn brick(&self) -> Brick {
+        Brick::new(self.kind)
+    }
+}
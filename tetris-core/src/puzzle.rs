@@ -0,0 +1,187 @@
+use std::str::FromStr;
+
+use crate::board::{empty_board, parse_row};
+use crate::constants::{BOARD_HEIGHT, MAX_BOARD_HEIGHT, MAX_BOARD_WIDTH, MIN_BOARD_WIDTH};
+use crate::enums::TetrominoKind;
+use crate::scoring::{ScoreEvent, Spin};
+use crate::types::Matrix;
+
+const HEADER: &str = "tetris-puzzle 1";
+
+/// What a puzzle has to be solved with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleGoal {
+    Lines(u32),
+    PerfectClear,
+    TSpinDouble,
+}
+
+impl PuzzleGoal {
+    /// Whether the lock that scored `event`, with `rows_cleared` lines cleared in all, solves
+    /// the puzzle.
+    pub fn is_met(self, event: &ScoreEvent, rows_cleared: u32) -> bool {
+        match self {
+            PuzzleGoal::Lines(lines) => rows_cleared >= lines,
+            PuzzleGoal::PerfectClear => event.perfect_clear,
+            PuzzleGoal::TSpinDouble => event.spin == Spin::Full && event.lines == 2,
+        }
+    }
+
+    /// Shown to the player, such as "Clear 4 lines".
+    pub fn description(self) -> String {
+        match self {
+            PuzzleGoal::Lines(1) => "Clear 1 line".to_string(),
+            PuzzleGoal::Lines(lines) => format!("Clear {} lines", lines),
+            PuzzleGoal::PerfectClear => "Perfect clear".to_string(),
+            PuzzleGoal::TSpinDouble => "T-spin double".to_string(),
+        }
+    }
+}
+
+impl FromStr for PuzzleGoal {
+    type Err = String;
+
+    /// `lines <count>`, `perfect_clear` or `tsd`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once(' ') {
+            Some(("lines", count)) => count
+                .parse()
+                .ok()
+                .filter(|&count| count > 0)
+                .map(PuzzleGoal::Lines)
+                .ok_or_else(|| format!("invalid line count '{}'", count)),
+            None if value == "perfect_clear" => Ok(PuzzleGoal::PerfectClear),
+            None if value == "tsd" => Ok(PuzzleGoal::TSpinDouble),
+            _ => Err(format!("unknown goal '{}'", value)),
+        }
+    }
+}
+
+/// A board to start from, the pieces to solve it with, in order, and the goal. Written as a
+/// header, `name`, `goal` and `pieces` lines, then `board` followed by the bottom rows of the
+/// board in the letters of saved games:
+///
+/// ```text
+/// tetris-puzzle 1
+/// name First Tetris
+/// goal lines 4
+/// pieces I
+/// board
+/// GGGGGGGGG.
+/// ```
+///
+/// The board is as wide as its rows and `BOARD_HEIGHT` high, or higher when more rows are given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    pub name: String,
+    pub goal: PuzzleGoal,
+    pub pieces: Vec<TetrominoKind>,
+    /// The visible board, without the hidden buffer.
+    pub board: Matrix,
+}
+
+impl Puzzle {
+    pub fn width(&self) -> usize {
+        self.board[0].len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.board.len()
+    }
+}
+
+impl FromStr for Puzzle {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut lines = text.lines();
+
+        if lines.next().map(str::trim_end) != Some(HEADER) {
+            return Err("not a puzzle file".to_string());
+        }
+
+        let mut field = |name: &str| -> Result<String, String> {
+            lines
+                .next()
+                .and_then(|line| line.trim_end().strip_prefix(name))
+                .and_then(|value| value.strip_prefix(' ').or(value.is_empty().then_some("")))
+                .map(str::to_string)
+                .ok_or_else(|| format!("missing {}", name))
+        };
+
+        let name = field("name")?;
+        let goal = field("goal")?.parse()?;
+        let pieces = field("pieces")?
+            .chars()
+            .map(|letter| TetrominoKind::from_letter(letter).ok_or_else(|| format!("unknown piece '{}'", letter)))
+            .collect::<Result<Vec<_>, _>>()?;
+        field("board")?;
+
+        if pieces.is_empty() {
+            return Err("a puzzle needs pieces".to_string());
+        }
+
+        let rows = lines
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_row(line).map_err(|letter| format!("unknown board letter '{}'", letter)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return Err("board rows differ in width".to_string());
+        }
+
+        if !(MIN_BOARD_WIDTH..=MAX_BOARD_WIDTH).contains(&width) || rows.len() > MAX_BOARD_HEIGHT {
+            return Err(format!("unsupported {}x{} board", width, rows.len()));
+        }
+
+        let mut board = empty_board(width, BOARD_HEIGHT.saturating_sub(rows.len()));
+        board.extend(rows);
+
+        Ok(Self {
+            name,
+            goal,
+            pieces,
+            board,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tetromino::Brick;
+
+    const TEXT: &str = "tetris-puzzle 1\nname First Tetris\ngoal lines 4\npieces IO\nboard\nGGGG.\nGGGG.\nGGGG.\nGGGG.\n";
+
+    #[test]
+    fn test_parse() {
+        let puzzle: Puzzle = TEXT.parse().unwrap();
+
+        assert_eq!(puzzle.name, "First Tetris");
+        assert_eq!(puzzle.goal, PuzzleGoal::Lines(4));
+        assert_eq!(puzzle.pieces, vec![TetrominoKind::I, TetrominoKind::O]);
+        assert_eq!((puzzle.width(), puzzle.height()), (5, BOARD_HEIGHT));
+        assert_eq!(puzzle.board[BOARD_HEIGHT - 1][0], Some(Brick::garbage()));
+        assert_eq!(puzzle.board[BOARD_HEIGHT - 1][4], None);
+        assert!(puzzle.board[BOARD_HEIGHT - 5].iter().all(|cell| cell.is_none()));
+    }
+
+    #[test]
+    fn test_goals() {
+        assert_eq!("perfect_clear".parse(), Ok(PuzzleGoal::PerfectClear));
+        assert_eq!("tsd".parse(), Ok(PuzzleGoal::TSpinDouble));
+        assert!("lines 0".parse::<PuzzleGoal>().is_err());
+        assert!("tetris".parse::<PuzzleGoal>().is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_puzzles() {
+        assert!("".parse::<Puzzle>().is_err());
+        assert!(TEXT.replace("pieces IO", "pieces").parse::<Puzzle>().is_err());
+        assert!(TEXT.replace("pieces IO", "pieces IX").parse::<Puzzle>().is_err());
+        assert!(TEXT.replace("GGGG.\nGGGG.\n", "GGG.\nGGGG.\n").parse::<Puzzle>().is_err());
+        assert!(TEXT.replace("GGGG.", "GG.").parse::<Puzzle>().is_err());
+    }
+}
@@ -0,0 +1,372 @@
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::bag::Bag;
+use crate::enums::TetrominoKind;
+
+/// A fresh seed for games that were not given one.
+pub fn random_seed() -> u64 {
+    rand::random()
+}
+
+/// Source of the piece sequence.
+pub trait Randomizer: Debug + Send {
+    fn next_kind(&mut self) -> TetrominoKind;
+}
+
+/// The randomizers a game can be started with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RandomizerKind {
+    #[default]
+    SevenBag,
+    FourteenBag,
+    PureRandom,
+    Nes,
+    Tgm1,
+    Tgm2,
+}
+
+impl RandomizerKind {
+    pub const ALL: [RandomizerKind; 6] = [
+        RandomizerKind::SevenBag,
+        RandomizerKind::FourteenBag,
+        RandomizerKind::PureRandom,
+        RandomizerKind::Nes,
+        RandomizerKind::Tgm1,
+        RandomizerKind::Tgm2,
+    ];
+
+    pub fn create(self, seed: u64) -> Box<dyn Randomizer> {
+        match self {
+            RandomizerKind::SevenBag => Box::new(Bag::with_seed(seed)),
+            RandomizerKind::FourteenBag => Box::new(Bag::with_copies(seed, 2)),
+            RandomizerKind::PureRandom => Box::new(PureRandom::new(seed)),
+            RandomizerKind::Nes => Box::new(Nes::new(seed)),
+            RandomizerKind::Tgm1 => Box::new(History::tgm1(seed)),
+            RandomizerKind::Tgm2 => Box::new(History::tgm2(seed)),
+        }
+    }
+
+    /// Name used on the command line and on screen.
+    pub fn name(self) -> &'static str {
+        match self {
+            RandomizerKind::SevenBag => "7bag",
+            RandomizerKind::FourteenBag => "14bag",
+            RandomizerKind::PureRandom => "random",
+            RandomizerKind::Nes => "nes",
+            RandomizerKind::Tgm1 => "tgm1",
+            RandomizerKind::Tgm2 => "tgm2",
+        }
+    }
+}
+
+impl FromStr for RandomizerKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        RandomizerKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+            .ok_or_else(|| format!("unknown randomizer '{}'", name))
+    }
+}
+
+fn random_kind(rng: &mut ChaCha8Rng) -> TetrominoKind {
+    TetrominoKind::ALL[rng.random_range(0..TetrominoKind::ALL.len())]
+}
+
+/// Every piece is drawn independently with the same probability.
+#[derive(Debug)]
+pub struct PureRandom {
+    rng: ChaCha8Rng,
+}
+
+impl PureRandom {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Randomizer for PureRandom {
+    fn next_kind(&mut self) -> TetrominoKind {
+        random_kind(&mut self.rng)
+    }
+}
+
+/// NES Tetris: rolls one of 8 values, and when that is the dummy value or repeats the previous
+/// piece, rolls once more among the 7 pieces and takes whatever comes up.
+#[derive(Debug)]
+pub struct Nes {
+    rng: ChaCha8Rng,
+    previous: Option<TetrominoKind>,
+}
+
+impl Nes {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            previous: None,
+        }
+    }
+}
+
+impl Randomizer for Nes {
+    fn next_kind(&mut self) -> TetrominoKind {
+        let roll = self.rng.random_range(0..=TetrominoKind::ALL.len());
+
+        let kind = match TetrominoKind::ALL.get(roll) {
+            Some(&kind) if Some(kind) != self.previous => kind,
+            _ => random_kind(&mut self.rng),
+        };
+
+        self.previous = Some(kind);
+        kind
+    }
+}
+
+/// TGM history randomizer: remembers the last 4 pieces and rolls up to `rolls` times for one
+/// that is not among them, keeping the last roll otherwise. The first piece is never S, Z or O.
+#[derive(Debug)]
+pub struct History {
+    rng: ChaCha8Rng,
+    history: [TetrominoKind; 4],
+    rolls: u32,
+    first: bool,
+}
+
+impl History {
+    /// The original TGM: 4 rolls, history starting as Z Z Z Z.
+    pub fn tgm1(seed: u64) -> Self {
+        Self::new(seed, 4, [TetrominoKind::Z; 4])
+    }
+
+    /// TGM2: 6 rolls, history starting as Z S S Z.
+    pub fn tgm2(seed: u64) -> Self {
+        Self::new(
+            seed,
+            6,
+            [TetrominoKind::Z, TetrominoKind::S, TetrominoKind::S, TetrominoKind::Z],
+        )
+    }
+
+    fn new(seed: u64, rolls: u32, history: [TetrominoKind; 4]) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            history,
+            rolls,
+            first: true,
+        }
+    }
+}
+
+impl Randomizer for History {
+    fn next_kind(&mut self) -> TetrominoKind {
+        let kind = if self.first {
+            self.first = false;
+
+            let first = [TetrominoKind::I, TetrominoKind::J, TetrominoKind::L, TetrominoKind::T];
+            first[self.rng.random_range(0..first.len())]
+        } else {
+            let mut kind = random_kind(&mut self.rng);
+
+            for _ in 1..self.rolls {
+                if !self.history.contains(&kind) {
+                    break;
+                }
+
+                kind = random_kind(&mut self.rng);
+            }
+
+            kind
+        };
+
+        self.history.rotate_left(1);
+        self.history[3] = kind;
+        kind
+    }
+}
+
+/// Deals a fixed sequence, as a puzzle does, starting over once it runs out.
+#[derive(Debug, Clone)]
+pub struct Scripted {
+    kinds: Vec<TetrominoKind>,
+    index: usize,
+}
+
+impl Scripted {
+    /// Panics when `kinds` is empty.
+    pub fn new(kinds: Vec<TetrominoKind>) -> Self {
+        assert!(!kinds.is_empty(), "a scripted sequence needs pieces");
+
+        Self { kinds, index: 0 }
+    }
+}
+
+impl Randomizer for Scripted {
+    fn next_kind(&mut self) -> TetrominoKind {
+        let kind = self.kinds[self.index % self.kinds.len()];
+        self.index += 1;
+        kind
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: usize = 70_000;
+
+    fn sequence(kind: RandomizerKind, seed: u64, count: usize) -> Vec<TetrominoKind> {
+        let mut randomizer = kind.create(seed);
+        (0..count).map(|_| randomizer.next_kind()).collect()
+    }
+
+    fn repeat_rate(pieces: &[TetrominoKind]) -> f64 {
+        let repeats = pieces.windows(2).filter(|pair| pair[0] == pair[1]).count();
+        repeats as f64 / (pieces.len() - 1) as f64
+    }
+
+    /// Longest run of pieces between two occurrences of the same kind.
+    fn longest_drought(pieces: &[TetrominoKind]) -> usize {
+        TetrominoKind::ALL
+            .iter()
+            .map(|kind| {
+                let positions: Vec<usize> = pieces
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, piece)| *piece == kind)
+                    .map(|(index, _)| index)
+                    .collect();
+
+                positions.windows(2).map(|pair| pair[1] - pair[0] - 1).max().unwrap_or(0)
+            })
+            .max()
+            .unwrap()
+    }
+
+    fn assert_uniform(pieces: &[TetrominoKind]) {
+        let expected = pieces.len() as f64 / 7.0;
+
+        for kind in TetrominoKind::ALL {
+            let count = pieces.iter().filter(|&&piece| piece == kind).count() as f64;
+            assert!(
+                (count - expected).abs() < expected * 0.05,
+                "{:?} drawn {} times, expected about {}",
+                kind,
+                count,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        for kind in RandomizerKind::ALL {
+            assert_eq!(sequence(kind, 9, 100), sequence(kind, 9, 100), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for kind in RandomizerKind::ALL {
+            assert_eq!(kind.name().parse::<RandomizerKind>(), Ok(kind));
+        }
+
+        assert!("8bag".parse::<RandomizerKind>().is_err());
+    }
+
+    #[test]
+    fn test_seven_bag() {
+        let pieces = sequence(RandomizerKind::SevenBag, 1, SAMPLES);
+
+        for bag in pieces.chunks(7) {
+            let mut kinds = bag.to_vec();
+            kinds.sort_by_key(|kind| *kind as u8);
+            assert_eq!(kinds, TetrominoKind::ALL.to_vec());
+        }
+
+        assert!(longest_drought(&pieces) <= 12);
+        assert!(pieces.windows(3).all(|run| !(run[0] == run[1] && run[1] == run[2])));
+    }
+
+    #[test]
+    fn test_fourteen_bag() {
+        let pieces = sequence(RandomizerKind::FourteenBag, 2, SAMPLES);
+
+        for bag in pieces.chunks(14) {
+            for kind in TetrominoKind::ALL {
+                assert_eq!(bag.iter().filter(|&&piece| piece == kind).count(), 2);
+            }
+        }
+
+        assert!(longest_drought(&pieces) <= 24);
+        assert!(pieces.windows(5).all(|run| run.iter().any(|&piece| piece != run[0])));
+    }
+
+    #[test]
+    fn test_pure_random() {
+        let pieces = sequence(RandomizerKind::PureRandom, 3, SAMPLES);
+
+        assert_uniform(&pieces);
+
+        let rate = repeat_rate(&pieces);
+        assert!((rate - 1.0 / 7.0).abs() < 0.01, "repeat rate {}", rate);
+        assert!(longest_drought(&pieces) > 24);
+    }
+
+    #[test]
+    fn test_nes_rerolls_repeats() {
+        let pieces = sequence(RandomizerKind::Nes, 4, SAMPLES);
+
+        assert_uniform(&pieces);
+
+        // A repeat needs the first roll to hit the previous piece or the dummy value and the
+        // reroll to hit the previous piece: 2/8 * 1/7.
+        let rate = repeat_rate(&pieces);
+        assert!((rate - 1.0 / 28.0).abs() < 0.005, "repeat rate {}", rate);
+    }
+
+    #[test]
+    fn test_tgm_history() {
+        for (kind, max_rate) in [(RandomizerKind::Tgm1, 0.03), (RandomizerKind::Tgm2, 0.01)] {
+            for seed in 0..50 {
+                let first = sequence(kind, seed, 1)[0];
+                assert!(
+                    matches!(
+                        first,
+                        TetrominoKind::I | TetrominoKind::J | TetrominoKind::L | TetrominoKind::T
+                    ),
+                    "{:?} started with {:?}",
+                    kind,
+                    first
+                );
+            }
+
+            let pieces = sequence(kind, 5, SAMPLES);
+            assert_uniform(&pieces);
+
+            let rate = repeat_rate(&pieces);
+            assert!(rate < max_rate, "{:?} repeat rate {}", kind, rate);
+        }
+
+        let tgm1 = longest_drought(&sequence(RandomizerKind::Tgm1, 6, SAMPLES));
+        let tgm2 = longest_drought(&sequence(RandomizerKind::Tgm2, 6, SAMPLES));
+        let random = longest_drought(&sequence(RandomizerKind::PureRandom, 6, SAMPLES));
+        assert!(tgm2 <= tgm1 && tgm1 < random, "droughts {} {} {}", tgm2, tgm1, random);
+    }
+
+    #[test]
+    fn test_scripted_deals_its_sequence() {
+        let kinds = vec![TetrominoKind::T, TetrominoKind::I, TetrominoKind::T];
+        let mut scripted = Scripted::new(kinds.clone());
+
+        let dealt: Vec<TetrominoKind> = (0..4).map(|_| scripted.next_kind()).collect();
+        assert_eq!(dealt[..3], kinds[..]);
+        assert_eq!(dealt[3], TetrominoKind::T);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::enums::{RotationState, TetrominoKind};
+use crate::piece::ActivePiece;
+use crate::types::Matrix;
+
+/// Kick offset as `(x, y)` with `x` to the right and `y` upwards, as in the SRS tables.
+type Kick = (isize, isize);
+
+// Rows are ordered 0->R, R->0, R->2, 2->R, 2->L, L->2, L->0, 0->L.
+const JLSTZ_KICKS: [[Kick; 5]; 8] = [
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+];
+
+const I_KICKS: [[Kick; 5]; 8] = [
+    [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
+    [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
+    [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
+    [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
+    [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
+    [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
+    [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
+    [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
+];
+
+const O_KICKS: [Kick; 1] = [(0, 0)];
+
+fn kick_table(kind: TetrominoKind, from: RotationState, to: RotationState) -> &'static [Kick] {
+    let index = match (from, to) {
+        (RotationState::Zero, RotationState::Right) => 0,
+        (RotationState::Right, RotationState::Zero) => 1,
+        (RotationState::Right, RotationState::Two) => 2,
+        (RotationState::Two, RotationState::Right) => 3,
+        (RotationState::Two, RotationState::Left) => 4,
+        (RotationState::Left, RotationState::Two) => 5,
+        (RotationState::Left, RotationState::Zero) => 6,
+        (RotationState::Zero, RotationState::Left) => 7,
+        _ => return &O_KICKS,
+    };
+
+    match kind {
+        TetrominoKind::O => &O_KICKS,
+        TetrominoKind::I => &I_KICKS[index],
+        _ => &JLSTZ_KICKS[index],
+    }
+}
+
+/// Rotates `piece` into `target` using the first kick that fits and returns that kick's index.
+fn rotate(game_space: &Matrix, piece: &mut ActivePiece, target: RotationState) -> Option<usize> {
+    if piece.kind == TetrominoKind::O {
+        piece.rotation = target;
+        return Some(0);
+    }
+
+    let kicks = kick_table(piece.kind, piece.rotation, target);
+
+    for (index, &(x, y)) in kicks.iter().enumerate() {
+        let rotated = ActivePiece {
+            rotation: target,
+            ..piece.shifted(x, -y)
+        };
+
+        if rotated.fits(game_space) {
+            *piece = rotated;
+            return Some(index);
+        }
+    }
+
+    None
+}
+
+pub fn rotate_clockwise(game_space: &Matrix, piece: &mut ActivePiece) -> Option<usize> {
+    rotate(game_space, piece, piece.rotation.clockwise())
+}
+
+pub fn rotate_counterclockwise(game_space: &Matrix, piece: &mut ActivePiece) -> Option<usize> {
+    rotate(game_space, piece, piece.rotation.counterclockwise())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tetromino::Brick;
+
+    const ORIGIN_ROW: isize = 8;
+    const ORIGIN_COL: isize = 3;
+
+    fn sorted_cells(piece: &ActivePiece) -> Vec<(isize, isize)> {
+        let mut cells = piece.cells().to_vec();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn test_kick_tables_for_every_piece_and_transition() {
+        let locked_brick = Some(Brick::new(TetrominoKind::O));
+        let kinds = [
+            TetrominoKind::I,
+            TetrominoKind::S,
+            TetrominoKind::Z,
+            TetrominoKind::T,
+            TetrominoKind::L,
+            TetrominoKind::J,
+        ];
+        let states = [
+            RotationState::Zero,
+            RotationState::Right,
+            RotationState::Two,
+            RotationState::Left,
+        ];
+
+        for kind in kinds {
+            for from in states {
+                for clockwise in [true, false] {
+                    let to = if clockwise {
+                        from.clockwise()
+                    } else {
+                        from.counterclockwise()
+                    };
+                    let kicks = kick_table(kind, from, to);
+                    assert_eq!(kicks.len(), 5);
+
+                    for expected in 0..kicks.len() {
+                        let mut piece = ActivePiece::new(kind, from, ORIGIN_COL, ORIGIN_ROW);
+                        let current = piece.cells();
+                        let target = |index: usize| {
+                            let (x, y) = kicks[index];
+                            ActivePiece::new(kind, to, ORIGIN_COL + x, ORIGIN_ROW - y)
+                        };
+                        let expected_cells = target(expected).cells();
+
+                        // Block every earlier kick with a single locked brick that does not
+                        // overlap the piece or the kick we expect to succeed. A kick whose cells
+                        // all lie inside those can never be reached, so it is skipped.
+                        let blockers: Option<Vec<(isize, isize)>> = (0..expected)
+                            .map(|earlier| {
+                                target(earlier).cells().into_iter().find(|cell| {
+                                    !current.contains(cell) && !expected_cells.contains(cell)
+                                })
+                            })
+                            .collect();
+
+                        let Some(blockers) = blockers else {
+                            continue;
+                        };
+
+                        let mut game_space: Matrix = vec![vec![None; 10]; 20];
+                        for (row, col) in blockers {
+                            game_space[row as usize][col as usize] = locked_brick;
+                        }
+
+                        let rotated = if clockwise {
+                            rotate_clockwise(&game_space, &mut piece)
+                        } else {
+                            rotate_counterclockwise(&game_space, &mut piece)
+                        };
+
+                        assert_eq!(rotated, Some(expected));
+                        assert_eq!(
+                            sorted_cells(&piece),
+                            sorted_cells(&target(expected)),
+                            "{:?} {:?}->{:?} should use kick #{}",
+                            kind,
+                            from,
+                            to,
+                            expected
+                        );
+                        assert_eq!(piece.rotation, to);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotation_fails_when_every_kick_is_blocked() {
+        let locked_brick = Some(Brick::new(TetrominoKind::O));
+        let mut piece = ActivePiece::new(TetrominoKind::T, RotationState::Zero, ORIGIN_COL, ORIGIN_ROW);
+
+        let mut game_space: Matrix = vec![vec![locked_brick; 10]; 20];
+        for (row, col) in piece.cells() {
+            game_space[row as usize][col as usize] = None;
+        }
+
+        let expected_piece = piece;
+
+        assert_eq!(rotate_clockwise(&game_space, &mut piece), None);
+        assert_eq!(piece, expected_piece);
+    }
+
+    #[test]
+    fn test_o_never_moves() {
+        let game_space: Matrix = vec![vec![None; 4]; 3];
+        let mut piece = ActivePiece::new(TetrominoKind::O, RotationState::Zero, 1, 1);
+        let expected_cells = sorted_cells(&piece);
+
+        assert!(rotate_clockwise(&game_space, &mut piece).is_some());
+        assert_eq!(sorted_cells(&piece), expected_cells);
+        assert_eq!(piece.rotation, RotationState::Right);
+
+        rotate_counterclockwise(&game_space, &mut piece);
+        rotate_counterclockwise(&game_space, &mut piece);
+        assert_eq!(sorted_cells(&piece), expected_cells);
+        assert_eq!(piece.rotation, RotationState::Left);
+    }
+
+    #[test]
+    fn test_i_rotates_against_left_wall() {
+        let game_space: Matrix = vec![vec![None; 6]; 6];
+        let mut piece = ActivePiece::new(TetrominoKind::I, RotationState::Left, -1, 1);
+        assert_eq!(sorted_cells(&piece), vec![(1, 0), (2, 0), (3, 0), (4, 0)]);
+
+        assert!(rotate_clockwise(&game_space, &mut piece).is_some());
+
+        assert_eq!(sorted_cells(&piece), vec![(2, 0), (2, 1), (2, 2), (2, 3)]);
+        assert_eq!(piece.rotation, RotationState::Zero);
+    }
+
+    #[test]
+    fn test_t_rotates_against_right_wall() {
+        let game_space: Matrix = vec![vec![None; 4]; 4];
+        let mut piece = ActivePiece::new(TetrominoKind::T, RotationState::Left, 2, 0);
+        assert_eq!(sorted_cells(&piece), vec![(0, 3), (1, 2), (1, 3), (2, 3)]);
+
+        assert!(rotate_clockwise(&game_space, &mut piece).is_some());
+
+        assert_eq!(sorted_cells(&piece), vec![(0, 2), (1, 1), (1, 2), (1, 3)]);
+        assert_eq!(piece.rotation, RotationState::Zero);
+    }
+}
@@ -0,0 +1,853 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fmt::Write;
+
+use crate::constants::{
+    BUFFER_HEIGHT, MASTER_MAX_LEVEL, MAX_BOARD_HEIGHT, MAX_BOARD_WIDTH, MAX_DIG_ROWS, MAX_MESSINESS,
+    MAX_PREVIEW_COUNT, MAX_START_LEVEL, MAX_ULTRA_MINUTES, MIN_BOARD_HEIGHT, MIN_BOARD_WIDTH,
+    MIN_ULTRA_MINUTES,
+};
+use crate::board::{parse_row, row_to_text};
+use crate::enums::{RotationState, TetrominoKind};
+use crate::game::{Game, GameOptions};
+use crate::garbage::GarbageHoles;
+use crate::lock_delay::LockDelay;
+use crate::mode::Mode;
+use crate::piece::ActivePiece;
+use crate::randomizer::RandomizerKind;
+use crate::scoring::Scoring;
+use crate::tetromino::{Brick, Tetromino};
+use crate::types::Matrix;
+
+/// Version 1 saves predate the hidden buffer rows; they are loaded with an empty buffer added.
+/// Versions 1 and 2 predate game modes and are loaded as marathon games. Versions before 4
+/// predate rising garbage and are loaded with its timer at zero, and versions before 5 predate
+/// master gravity and entry delays.
+pub const SAVE_VERSION: u32 = 5;
+const HEADER: &str = "tetris-save";
+/// Most pieces or garbage rows a save can have drawn. Far more than anyone plays in a game, and
+/// few enough that replaying the draws of a corrupt count does not stall loading.
+const MAX_DRAWN: u64 = 1_000_000;
+/// Highest level a save can have reached outside master, which ends at `MASTER_MAX_LEVEL`. Far
+/// beyond the last speed change, and low enough that scoring at it cannot overflow.
+const MAX_LEVEL: u32 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    /// Written by a newer or older build that this one cannot read.
+    UnsupportedVersion(u32),
+    Corrupt(String),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::UnsupportedVersion(version) => {
+                write!(f, "unsupported save version {} (expected {})", version, SAVE_VERSION)
+            }
+            LoadError::Corrupt(reason) => write!(f, "corrupt save: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+fn corrupt(reason: impl Into<String>) -> LoadError {
+    LoadError::Corrupt(reason.into())
+}
+
+fn parse_kind(letter: char) -> Result<TetrominoKind, LoadError> {
+    TetrominoKind::from_letter(letter).ok_or_else(|| corrupt(format!("unknown piece '{}'", letter)))
+}
+
+fn rotation_name(rotation: RotationState) -> &'static str {
+    match rotation {
+        RotationState::Zero => "0",
+        RotationState::Right => "R",
+        RotationState::Two => "2",
+        RotationState::Left => "L",
+    }
+}
+
+fn parse_rotation(name: &str) -> Result<RotationState, LoadError> {
+    match name {
+        "0" => Ok(RotationState::Zero),
+        "R" => Ok(RotationState::Right),
+        "2" => Ok(RotationState::Two),
+        "L" => Ok(RotationState::Left),
+        _ => Err(corrupt(format!("unknown rotation '{}'", name))),
+    }
+}
+
+/// The mode's name followed by its settings, such as `ultra 3` for a three minute ultra,
+/// `marathon 150 5` for a 150 line marathon started at level 5 or `dig 10 30` for ten garbage
+/// rows at 30% messiness. Survival only keeps its messiness, and zen whether gravity is `on`.
+fn mode_settings(options: &GameOptions) -> String {
+    match options.mode {
+        Mode::Marathon => format!(
+            "{} {} {}",
+            options.mode.name(),
+            options.marathon_goal.name(),
+            options.start_level
+        ),
+        Mode::Ultra => format!("{} {}", options.mode.name(), options.ultra_minutes),
+        Mode::Dig => format!("{} {} {}", options.mode.name(), options.dig_rows, options.messiness),
+        Mode::Survival => format!("{} {}", options.mode.name(), options.messiness),
+        Mode::Zen => format!("{} {}", options.mode.name(), if options.gravity { "on" } else { "off" }),
+        mode => mode.name().to_string(),
+    }
+}
+
+/// Reads the mode settings into the default options. Marathons saved before they had settings
+/// are endless games from `DEFAULT_LEVEL`.
+fn parse_mode_settings(value: &str) -> Result<GameOptions, LoadError> {
+    let invalid = || corrupt(format!("invalid mode '{}'", value));
+    let mut parts = value.split(' ');
+    let mut options = GameOptions {
+        mode: parts.next().unwrap_or_default().parse().map_err(corrupt)?,
+        ..GameOptions::default()
+    };
+
+    match options.mode {
+        Mode::Marathon => {
+            if let Some(goal) = parts.next() {
+                options.marathon_goal = goal.parse().map_err(corrupt)?;
+                options.start_level = parts
+                    .next()
+                    .and_then(|level| level.parse().ok())
+                    .filter(|level| *level <= MAX_START_LEVEL)
+                    .ok_or_else(invalid)?;
+            }
+        }
+        Mode::Ultra => {
+            options.ultra_minutes = parts
+                .next()
+                .and_then(|minutes| minutes.parse().ok())
+                .filter(|minutes| (MIN_ULTRA_MINUTES..=MAX_ULTRA_MINUTES).contains(minutes))
+                .ok_or_else(invalid)?;
+        }
+        Mode::Dig => {
+            options.dig_rows = parts
+                .next()
+                .and_then(|rows| rows.parse().ok())
+                .filter(|rows| (1..=MAX_DIG_ROWS).contains(rows))
+                .ok_or_else(invalid)?;
+            options.messiness = parse_messiness(parts.next()).ok_or_else(invalid)?;
+        }
+        Mode::Survival => options.messiness = parse_messiness(parts.next()).ok_or_else(invalid)?,
+        Mode::Zen => {
+            options.gravity = match parts.next() {
+                Some("on") => true,
+                Some("off") => false,
+                _ => return Err(invalid()),
+            };
+        }
+        Mode::Puzzle => return Err(corrupt("puzzles cannot be resumed")),
+        Mode::Sprint | Mode::Master => {}
+    }
+
+    Ok(options)
+}
+
+fn parse_messiness(value: Option<&str>) -> Option<u32> {
+    value
+        .and_then(|messiness| messiness.parse().ok())
+        .filter(|messiness| *messiness <= MAX_MESSINESS)
+}
+
+impl Game {
+    /// Serializes the game as versioned, line-based text. The randomizer is stored as its kind,
+    /// seed and the number of pieces it has produced, which rebuilds the exact bag contents on
+    /// load. Lock delay progress and the last score event are not kept.
+    pub fn to_save(&self) -> String {
+        let mut text = String::new();
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+
+        let _ = writeln!(text, "{} {}", HEADER, SAVE_VERSION);
+        let _ = writeln!(text, "randomizer {}", self.options.randomizer.name());
+        let _ = writeln!(text, "mode {}", mode_settings(&self.options));
+        let _ = writeln!(text, "seed {}", self.seed);
+        let _ = writeln!(text, "drawn {}", self.pieces_drawn);
+        let _ = writeln!(text, "score {}", self.score);
+        let _ = writeln!(text, "level {}", self.level);
+        let _ = writeln!(text, "rows_cleared {}", self.rows_cleared);
+        let _ = writeln!(text, "elapsed {}", self.elapsed_ms);
+        let _ = writeln!(text, "pieces {}", self.pieces_placed);
+        let _ = writeln!(text, "finesse {}", self.finesse_faults);
+        let _ = writeln!(
+            text,
+            "line_times {}",
+            self.line_times.iter().map(|time| time.to_string()).collect::<Vec<_>>().join(" ")
+        );
+        let _ = writeln!(text, "garbage {} {}", self.garbage_drawn, self.garbage_timer_ms);
+        let _ = writeln!(text, "gravity {}", self.gravity_progress);
+        let _ = writeln!(text, "entry_delay {}", optional(self.entry_delay_ms.map(|delay| delay.to_string())));
+        let _ = writeln!(text, "back_to_back {}", self.scoring.back_to_back as u8);
+        let _ = writeln!(text, "combo {}", optional(self.scoring.combo.map(|combo| combo.to_string())));
+        let _ = writeln!(text, "lock_delay {} {}", self.lock_delay.delay_ms, self.lock_delay.max_resets);
+        let _ = writeln!(text, "hold_used {}", self.hold_used as u8);
+        let _ = writeln!(
+            text,
+            "hold {}",
+            optional(self.hold_item.as_ref().map(|item| item.kind.letter().to_string()))
+        );
+        let _ = writeln!(
+            text,
+            "active {}",
+            optional(self.active_piece.map(|piece| format!(
+                "{} {} {} {}",
+                piece.kind.letter(),
+                rotation_name(piece.rotation),
+                piece.x,
+                piece.y
+            )))
+        );
+        let _ = writeln!(
+            text,
+            "next {}",
+            self.next_items.iter().map(|item| item.kind.letter()).collect::<String>()
+        );
+        let _ = writeln!(text, "board");
+
+        for row in &self.game_space {
+            let _ = writeln!(text, "{}", row_to_text(row));
+        }
+
+        text
+    }
+
+    /// Restores a game written by [`Game::to_save`].
+    pub fn from_save(text: &str) -> Result<Game, LoadError> {
+        let mut lines = text.lines();
+
+        let header = lines.next().ok_or_else(|| corrupt("empty file"))?;
+        let version = header
+            .strip_prefix(HEADER)
+            .and_then(|version| version.trim().parse::<u32>().ok())
+            .ok_or_else(|| corrupt("missing header"))?;
+
+        if !(1..=SAVE_VERSION).contains(&version) {
+            return Err(LoadError::UnsupportedVersion(version));
+        }
+
+        let added_rows = if version == 1 { BUFFER_HEIGHT } else { 0 };
+
+        let mut field = |name: &str| -> Result<String, LoadError> {
+            let line = lines.next().ok_or_else(|| corrupt(format!("missing {}", name)))?;
+            let value = line
+                .strip_prefix(name)
+                .and_then(|value| value.strip_prefix(' ').or(value.is_empty().then_some("")))
+                .ok_or_else(|| corrupt(format!("expected {}, found '{}'", name, line)))?;
+
+            Ok(value.to_string())
+        };
+
+        fn number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, LoadError> {
+            value
+                .parse()
+                .map_err(|_| corrupt(format!("invalid {} '{}'", name, value)))
+        }
+
+        let randomizer_kind: RandomizerKind = field("randomizer")?.parse().map_err(corrupt)?;
+        let mode_options = match version {
+            1 | 2 => GameOptions::default(),
+            _ => parse_mode_settings(&field("mode")?)?,
+        };
+        let seed: u64 = number("seed", &field("seed")?)?;
+        let pieces_drawn: u64 = number("drawn", &field("drawn")?)?;
+        let score = number("score", &field("score")?)?;
+        let level: u32 = number("level", &field("level")?)?;
+        let rows_cleared: u32 = number("rows_cleared", &field("rows_cleared")?)?;
+
+        let (mut elapsed_ms, mut pieces_placed, mut finesse_faults, mut line_times) = (0, 0, 0, Vec::new());
+        if version >= 3 {
+            elapsed_ms = number("elapsed", &field("elapsed")?)?;
+            pieces_placed = number("pieces", &field("pieces")?)?;
+            finesse_faults = number("finesse", &field("finesse")?)?;
+            line_times = field("line_times")?
+                .split_whitespace()
+                .map(|time| number("line_times", time))
+                .collect::<Result<_, _>>()?;
+        }
+
+        let (mut garbage_drawn, mut garbage_timer_ms) = (None, 0);
+        if version >= 4 {
+            let garbage = field("garbage")?;
+            let (drawn, timer_ms) = garbage.split_once(' ').ok_or_else(|| corrupt("invalid garbage"))?;
+            garbage_drawn = Some(number::<u64>("garbage", drawn)?);
+            garbage_timer_ms = number("garbage", timer_ms)?;
+        }
+
+        let (mut gravity_progress, mut entry_delay_ms) = (0, None);
+        if version >= 5 {
+            gravity_progress = number("gravity", &field("gravity")?)?;
+            entry_delay_ms = match field("entry_delay")?.as_str() {
+                "-" => None,
+                value => Some(number("entry_delay", value)?),
+            };
+        }
+
+        let back_to_back = number::<u8>("back_to_back", &field("back_to_back")?)? != 0;
+
+        let combo = match field("combo")?.as_str() {
+            "-" => None,
+            value => Some(number("combo", value)?),
+        };
+
+        let lock_delay = field("lock_delay")?;
+        let (delay_ms, max_resets) = lock_delay
+            .split_once(' ')
+            .ok_or_else(|| corrupt("invalid lock_delay"))?;
+        let lock_delay = LockDelay::new(number("lock_delay", delay_ms)?, number("lock_delay", max_resets)?);
+
+        let hold_used = number::<u8>("hold_used", &field("hold_used")?)? != 0;
+
+        let hold_item = match field("hold")?.as_str() {
+            "-" => None,
+            value => {
+                let mut letters = value.chars();
+                let (Some(letter), None) = (letters.next(), letters.next()) else {
+                    return Err(corrupt(format!("invalid hold '{}'", value)));
+                };
+                Some(Tetromino::from_kind(parse_kind(letter)?))
+            }
+        };
+
+        let active_piece = match field("active")?.as_str() {
+            "-" => None,
+            value => {
+                let parts: Vec<&str> = value.split(' ').collect();
+                let [kind, rotation, x, y] = parts[..] else {
+                    return Err(corrupt("invalid active piece"));
+                };
+                let kind = parse_kind(kind.chars().next().ok_or_else(|| corrupt("invalid active piece"))?)?;
+
+                let x: isize = number("active x", x)?;
+                let y: isize = number("active y", y)?;
+
+                Some((kind, parse_rotation(rotation)?, x, y))
+            }
+        };
+
+        let next_items: VecDeque<Tetromino> = field("next")?
+            .chars()
+            .map(|letter| parse_kind(letter).map(Tetromino::from_kind))
+            .collect::<Result<_, _>>()?;
+
+        if next_items.len() != MAX_PREVIEW_COUNT {
+            return Err(corrupt(format!("{} next pieces instead of {}", next_items.len(), MAX_PREVIEW_COUNT)));
+        }
+
+        let max_level = match mode_options.mode {
+            Mode::Master => MASTER_MAX_LEVEL,
+            _ => MAX_LEVEL,
+        };
+        if level > max_level {
+            return Err(corrupt(format!("level {} is above {}", level, max_level)));
+        }
+
+        field("board")?;
+
+        let board_rows: Matrix = lines
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_row(line).map_err(|letter| corrupt(format!("unknown piece '{}'", letter))))
+            .collect::<Result<_, _>>()?;
+
+        let width = board_rows.first().map_or(0, |row: &Vec<Option<Brick>>| row.len());
+        if board_rows.iter().any(|row| row.len() != width) {
+            return Err(corrupt("board rows differ in width"));
+        }
+
+        let mut game_space = vec![vec![None; width]; added_rows];
+        game_space.extend(board_rows);
+
+        let height = game_space.len().saturating_sub(BUFFER_HEIGHT);
+        if width < MIN_BOARD_WIDTH || height < MIN_BOARD_HEIGHT {
+            return Err(corrupt(format!("{}x{} board is too small", width, height)));
+        }
+        if width > MAX_BOARD_WIDTH || height > MAX_BOARD_HEIGHT {
+            return Err(corrupt(format!("{}x{} board is too large", width, height)));
+        }
+
+        // Pieces are drawn into a 4x4 box, so the box starts at most 3 cells left of or above the
+        // board.
+        let active_piece = match active_piece {
+            Some((kind, rotation, x, y)) => {
+                let y = y.saturating_add(added_rows as isize);
+
+                if !(-3..width as isize).contains(&x) || !(-3..game_space.len() as isize).contains(&y) {
+                    return Err(corrupt(format!("active piece at {} {} is off the board", x, y)));
+                }
+
+                Some(ActivePiece::new(kind, rotation, x, y))
+            }
+            None => None,
+        };
+
+        if active_piece.is_some_and(|piece| !piece.fits(&game_space)) {
+            return Err(corrupt("active piece overlaps the board"));
+        }
+
+        // Every piece drawn is still coming, in play, on hold or was locked, and the cells of
+        // locked pieces are on the board or in cleared rows. Only modes that clear the board on
+        // topping out lose cells otherwise. The extra piece is one that could not spawn.
+        let filled = game_space.iter().flatten().filter(|cell| cell.is_some()).count() as u64;
+        let locked = (filled + rows_cleared as u64 * width as u64) / 4;
+        let in_play = next_items.len() as u64 + 3;
+
+        if pieces_drawn > MAX_DRAWN || (mode_options.mode.tops_out() && pieces_drawn > locked + in_play) {
+            return Err(corrupt(format!("{} pieces drawn do not fit the board", pieces_drawn)));
+        }
+
+        // Garbage rows drawn are still on the board or were cleared.
+        if let Some(drawn) = garbage_drawn
+            && (drawn > MAX_DRAWN || drawn > game_space.len() as u64 + rows_cleared as u64)
+        {
+            return Err(corrupt(format!("{} garbage rows drawn do not fit the board", drawn)));
+        }
+
+        let mut randomizer = randomizer_kind.create(seed);
+        for _ in 0..pieces_drawn {
+            randomizer.next_kind();
+        }
+
+        let options = GameOptions {
+            width,
+            height,
+            randomizer: randomizer_kind,
+            ..mode_options
+        };
+
+        let mut game = Game::with_options(options, seed);
+        game.randomizer = randomizer;
+        game.pieces_drawn = pieces_drawn;
+        game.game_space = game_space;
+        game.next_items = next_items;
+        game.hold_item = hold_item;
+        game.hold_used = hold_used;
+        game.lock_delay = lock_delay;
+        game.level = level;
+        game.rows_cleared = rows_cleared;
+        game.score = score;
+        game.elapsed_ms = elapsed_ms;
+        game.pieces_placed = pieces_placed;
+        game.finesse_faults = finesse_faults;
+        game.line_times = line_times;
+        game.garbage_timer_ms = garbage_timer_ms;
+        game.gravity_progress = gravity_progress;
+        game.entry_delay_ms = entry_delay_ms;
+
+        // Older saves only hold the garbage a dig starts with, which the new game drew already.
+        if let Some(drawn) = garbage_drawn {
+            game.garbage_holes = GarbageHoles::new(seed, options.messiness);
+            game.garbage_holes.next_holes(drawn as usize, width);
+            game.garbage_drawn = drawn;
+        }
+        game.scoring = Scoring {
+            back_to_back,
+            combo,
+        };
+        game.active_piece = active_piece;
+        game.game_over = active_piece.is_none() && entry_delay_ms.is_none();
+
+        if let Some(piece) = active_piece {
+            game.lock_delay.start(piece.y);
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Input;
+    use crate::mode::MarathonGoal;
+
+    fn played_game() -> Game {
+        let mut game = Game::with_randomizer(RandomizerKind::Tgm1, 99);
+
+        for input in [
+            Input::HardDrop,
+            Input::Hold,
+            Input::Left,
+            Input::HardDrop,
+            Input::RotateClockwise,
+            Input::Right,
+            Input::Right,
+            Input::HardDrop,
+            Input::SoftDrop,
+        ] {
+            game.step(input);
+        }
+
+        game
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let game = played_game();
+        let restored = Game::from_save(&game.to_save()).unwrap();
+
+        assert_eq!(restored.game_space, game.game_space);
+        assert_eq!(restored.active_piece, game.active_piece);
+        assert_eq!(restored.next_items, game.next_items);
+        assert_eq!(restored.hold_item, game.hold_item);
+        assert_eq!(restored.hold_used, game.hold_used);
+        assert_eq!(restored.score, game.score);
+        assert_eq!(restored.level, game.level);
+        assert_eq!(restored.rows_cleared, game.rows_cleared);
+        assert_eq!(restored.scoring, game.scoring);
+        assert_eq!(restored.seed(), 99);
+        assert_eq!(restored.randomizer_kind(), RandomizerKind::Tgm1);
+        assert_eq!(restored.pieces_placed, game.pieces_placed);
+        assert_eq!(restored.to_save(), game.to_save());
+    }
+
+    #[test]
+    fn test_restored_game_continues_the_same_sequence() {
+        let mut game = played_game();
+        let mut restored = Game::from_save(&game.to_save()).unwrap();
+
+        for _ in 0..20 {
+            game.step(Input::HardDrop);
+            restored.step(Input::HardDrop);
+        }
+
+        assert_eq!(restored.game_space, game.game_space);
+        assert_eq!(restored.next_items, game.next_items);
+        assert_eq!(restored.active_piece, game.active_piece);
+    }
+
+    #[test]
+    fn test_board_size_is_kept() {
+        let options = GameOptions {
+            width: 6,
+            height: 30,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 3);
+        game.step(Input::HardDrop);
+
+        let restored = Game::from_save(&game.to_save()).unwrap();
+
+        assert_eq!((restored.width(), restored.height()), (6, 30));
+        assert_eq!(restored.game_space, game.game_space);
+    }
+
+    #[test]
+    fn test_sprint_progress_is_kept() {
+        let options = GameOptions {
+            mode: Mode::Sprint,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 4);
+        game.advance(1234);
+        game.step(Input::Left);
+        game.step(Input::RotateClockwise);
+        game.step(Input::HardDrop);
+        game.line_times = vec![900, 1100];
+
+        let restored = Game::from_save(&game.to_save()).unwrap();
+
+        assert_eq!(restored.mode(), Mode::Sprint);
+        assert_eq!(restored.elapsed_ms, 1234);
+        assert_eq!(restored.pieces_placed, 1);
+        assert_eq!(restored.finesse_faults, game.finesse_faults);
+        assert_eq!(restored.line_times, vec![900, 1100]);
+    }
+
+    #[test]
+    fn test_ultra_length_is_kept() {
+        let options = GameOptions {
+            mode: Mode::Ultra,
+            ultra_minutes: 2,
+            ..GameOptions::default()
+        };
+        let text = Game::with_options(options, 4).to_save();
+
+        assert!(text.contains("\nmode ultra 2\n"));
+        assert_eq!(Game::from_save(&text).unwrap().options(), options);
+        assert!(Game::from_save(&text.replace("mode ultra 2", "mode ultra 9")).is_err());
+    }
+
+    #[test]
+    fn test_marathon_settings_are_kept() {
+        let options = GameOptions {
+            marathon_goal: MarathonGoal::Lines150,
+            start_level: 12,
+            ..GameOptions::default()
+        };
+        let text = Game::with_options(options, 4).to_save();
+
+        assert!(text.contains("\nmode marathon 150 12\n"));
+        assert_eq!(Game::from_save(&text).unwrap().options(), options);
+        assert!(Game::from_save(&text.replace("marathon 150 12", "marathon 150 30")).is_err());
+
+        // Saves from before marathon settings.
+        let restored = Game::from_save(&text.replace("marathon 150 12", "marathon")).unwrap();
+        assert_eq!(restored.options(), GameOptions::default());
+    }
+
+    #[test]
+    fn test_dig_garbage_is_kept() {
+        let options = GameOptions {
+            mode: Mode::Dig,
+            dig_rows: 4,
+            messiness: 30,
+            ..GameOptions::default()
+        };
+        let game = Game::with_options(options, 4);
+        let text = game.to_save();
+        let restored = Game::from_save(&text).unwrap();
+
+        assert!(text.contains("\nmode dig 4 30\n"));
+        assert_eq!(restored.options(), options);
+        assert_eq!(restored.game_space, game.game_space);
+        assert_eq!(restored.garbage_remaining(), 4);
+        assert!(Game::from_save(&text.replace("dig 4 30", "dig 4 101")).is_err());
+        assert!(Game::from_save(&text.replace("dig 4 30", "dig 0 30")).is_err());
+    }
+
+    #[test]
+    fn test_rising_garbage_is_kept() {
+        let options = GameOptions {
+            mode: Mode::Survival,
+            messiness: 60,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 6);
+        game.advance(25_000);
+
+        let text = game.to_save();
+        let mut restored = Game::from_save(&text).unwrap();
+
+        assert!(text.contains("\nmode survival 60\n"));
+        assert!(text.contains("\ngarbage 2 5000\n"));
+        assert_eq!(restored.options(), options);
+        assert_eq!(restored.garbage_timer_ms, 5_000);
+
+        // The next rows get the same holes as they would have without saving.
+        game.advance(50_000);
+        restored.advance(50_000);
+        assert_eq!(restored.game_space, game.game_space);
+
+        // Saves from before rising garbage.
+        let old = text
+            .replacen(&format!("tetris-save {}", SAVE_VERSION), "tetris-save 3", 1)
+            .replace("garbage 2 5000\n", "")
+            .replace("gravity 0\nentry_delay -\n", "");
+        assert_eq!(Game::from_save(&old).unwrap().garbage_timer_ms, 0);
+    }
+
+    #[test]
+    fn test_zen_gravity_is_kept() {
+        let options = GameOptions {
+            mode: Mode::Zen,
+            gravity: false,
+            ..GameOptions::default()
+        };
+        let text = Game::with_options(options, 4).to_save();
+
+        assert!(text.contains("\nmode zen off\n"));
+        assert_eq!(Game::from_save(&text).unwrap().options(), options);
+        assert!(Game::from_save(&text.replace("zen off", "zen")).is_err());
+    }
+
+    #[test]
+    fn test_puzzles_cannot_be_resumed() {
+        let puzzle = "tetris-puzzle 1\nname Test\ngoal tsd\npieces T\nboard\nGGG.GGGGGG\n".parse().unwrap();
+        let text = Game::from_puzzle(puzzle).to_save();
+
+        assert!(text.contains("\nmode puzzle\n"));
+        assert!(Game::from_save(&text).is_err());
+    }
+
+    #[test]
+    fn test_master_entry_delay_is_kept() {
+        let options = GameOptions {
+            mode: Mode::Master,
+            ..GameOptions::default()
+        };
+        let mut game = Game::with_options(options, 4);
+        game.advance(500);
+        game.step(Input::HardDrop);
+        game.advance(100);
+
+        let text = game.to_save();
+        let mut restored = Game::from_save(&text).unwrap();
+
+        assert!(text.contains("\nmode master\n"));
+        assert!(text.contains("\nentry_delay 316\n"));
+        assert!(!restored.game_over);
+        assert_eq!(restored.level, 1);
+
+        game.advance(2000);
+        restored.advance(2000);
+        assert_eq!(restored.active_piece, game.active_piece);
+    }
+
+    /// Rewrites a current save the way version 2 wrote it, without a mode, sprint stats and
+    /// garbage.
+    fn as_version_two(text: &str) -> String {
+        let stats = ["mode ", "elapsed ", "pieces ", "finesse ", "line_times", "garbage ", "gravity ", "entry_delay "];
+
+        text.replacen(&format!("tetris-save {}", SAVE_VERSION), "tetris-save 2", 1)
+            .lines()
+            .filter(|line| !stats.iter().any(|field| line.starts_with(field)))
+            .map(|line| format!("{}\n", line))
+            .collect()
+    }
+
+    #[test]
+    fn test_version_two_is_a_marathon() {
+        let game = played_game();
+        let restored = Game::from_save(&as_version_two(&game.to_save())).unwrap();
+
+        assert_eq!(restored.mode(), Mode::Marathon);
+        assert_eq!(restored.game_space, game.game_space);
+        assert_eq!(restored.elapsed_ms, 0);
+    }
+
+    #[test]
+    fn test_version_one_gets_a_buffer() {
+        let game = played_game();
+        let text = as_version_two(&game.to_save()).replacen("tetris-save 2", "tetris-save 1", 1);
+
+        // Drop the buffer rows and move the piece up by as much, as a version 1 save had them.
+        let (head, board) = text.split_once("board\n").unwrap();
+        let piece = game.active_piece.unwrap();
+        let head = head.replace(
+            &format!("{} {}\n", piece.x, piece.y),
+            &format!("{} {}\n", piece.x, piece.y - BUFFER_HEIGHT as isize),
+        );
+        let board: Vec<&str> = board.lines().skip(BUFFER_HEIGHT).collect();
+        let text = format!("{}board\n{}\n", head, board.join("\n"));
+
+        let restored = Game::from_save(&text).unwrap();
+        assert_eq!(restored.game_space, game.game_space);
+        assert_eq!(restored.active_piece, game.active_piece);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let text = played_game().to_save().replacen(&format!("tetris-save {}", SAVE_VERSION), "tetris-save 7", 1);
+
+        assert_eq!(Game::from_save(&text).unwrap_err(), LoadError::UnsupportedVersion(7));
+    }
+
+    #[test]
+    fn test_corrupt_saves_are_rejected() {
+        let text = played_game().to_save();
+
+        assert!(Game::from_save("").is_err());
+        assert!(Game::from_save("hello").is_err());
+        assert!(Game::from_save(&text.replacen("score ", "score x", 1)).is_err());
+        assert!(Game::from_save(&text.replacen("randomizer tgm1", "randomizer 9bag", 1)).is_err());
+        assert!(Game::from_save(&text.replacen("next ", "next Q", 1)).is_err());
+
+        let truncated: String = text.lines().take(8).map(|line| format!("{}\n", line)).collect();
+        assert!(Game::from_save(&truncated).is_err());
+
+        let narrow = Game::from_save(&text.replace("..........\n", "...\n"));
+        assert!(narrow.is_err());
+
+        let ragged = format!("{}.\n", text);
+        assert!(matches!(Game::from_save(&ragged), Err(LoadError::Corrupt(_))));
+    }
+
+    /// Replaces the value of the `name` field in `text`.
+    fn with_field(text: &str, name: &str, value: &str) -> String {
+        text.lines()
+            .map(|line| {
+                if line.split(' ').next() == Some(name) {
+                    format!("{} {}\n", name, value)
+                } else {
+                    format!("{}\n", line)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_impossible_piece_counts_are_rejected() {
+        let text = played_game().to_save();
+
+        let huge = with_field(&text, "drawn", &u64::MAX.to_string());
+        assert!(matches!(Game::from_save(&huge), Err(LoadError::Corrupt(_))));
+
+        // More pieces than the board and the cleared lines can hold.
+        let many = with_field(&text, "drawn", "200");
+        assert!(matches!(Game::from_save(&many), Err(LoadError::Corrupt(_))));
+    }
+
+    #[test]
+    fn test_impossible_garbage_counts_are_rejected() {
+        let options = GameOptions {
+            mode: Mode::Dig,
+            ..GameOptions::default()
+        };
+        let text = Game::with_options(options, 8).to_save();
+        assert!(Game::from_save(&text).is_ok());
+
+        let huge = with_field(&text, "garbage", &format!("{} 0", u64::MAX));
+        assert!(matches!(Game::from_save(&huge), Err(LoadError::Corrupt(_))));
+    }
+
+    #[test]
+    fn test_active_piece_off_the_board_is_rejected() {
+        let text = played_game().to_save();
+
+        for position in ["10 20", "-4 20", "3 -4", "3 40", &format!("{} {}", isize::MAX, isize::MAX)] {
+            let moved = with_field(&text, "active", &format!("T 0 {}", position));
+            assert!(matches!(Game::from_save(&moved), Err(LoadError::Corrupt(_))), "{}", position);
+        }
+    }
+
+    #[test]
+    fn test_impossible_levels_are_rejected() {
+        let text = played_game().to_save();
+
+        assert!(Game::from_save(&with_field(&text, "level", "10000")).is_ok());
+        assert!(matches!(Game::from_save(&with_field(&text, "level", "10001")), Err(LoadError::Corrupt(_))));
+        let huge = with_field(&text, "level", &u32::MAX.to_string());
+        assert!(matches!(Game::from_save(&huge), Err(LoadError::Corrupt(_))));
+
+        let options = GameOptions {
+            mode: Mode::Master,
+            ..GameOptions::default()
+        };
+        let master = Game::with_options(options, 4).to_save();
+        assert!(Game::from_save(&with_field(&master, "level", "999")).is_ok());
+        assert!(matches!(Game::from_save(&with_field(&master, "level", "1000")), Err(LoadError::Corrupt(_))));
+    }
+
+    #[test]
+    fn test_oversized_boards_are_rejected() {
+        let text = played_game().to_save();
+        let (head, board) = text.split_once("board\n").unwrap();
+
+        let wide: String = board.lines().map(|row| format!("{}{}\n", row, ".".repeat(MAX_BOARD_WIDTH))).collect();
+        let wide = Game::from_save(&format!("{}board\n{}", head, wide));
+        assert!(matches!(wide, Err(LoadError::Corrupt(_))));
+
+        let tall = format!("{}{}", text, "..........\n".repeat(MAX_BOARD_HEIGHT));
+        assert!(matches!(Game::from_save(&tall), Err(LoadError::Corrupt(_))));
+    }
+
+    #[test]
+    fn test_malformed_queue_and_hold_are_rejected() {
+        let text = played_game().to_save();
+
+        for next in ["", "IJLOS", "IJLOSTZ"] {
+            let queue = with_field(&text, "next", next);
+            assert!(matches!(Game::from_save(&queue), Err(LoadError::Corrupt(_))), "{}", next);
+        }
+
+        assert!(Game::from_save(&with_field(&text, "hold", "I")).is_ok());
+        for hold in ["", "IJ", "Q"] {
+            let held = with_field(&text, "hold", hold);
+            assert!(matches!(Game::from_save(&held), Err(LoadError::Corrupt(_))), "{}", hold);
+        }
+    }
+}
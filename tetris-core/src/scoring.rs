@@ -0,0 +1,371 @@
+use crate::enums::{RotationState, TetrominoKind};
+use crate::piece::ActivePiece;
+use crate::types::Matrix;
+
+/// Index of the last SRS kick, the one that turns a mini T-spin into a full one.
+const LAST_KICK: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Spin {
+    #[default]
+    None,
+    Mini,
+    Full,
+}
+
+/// What a single lock earned and why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScoreEvent {
+    pub lines: u32,
+    pub spin: Spin,
+    pub back_to_back: bool,
+    pub combo: u32,
+    pub perfect_clear: bool,
+    pub points: u32,
+}
+
+impl ScoreEvent {
+    /// Short description such as "T-Spin Double" or "Tetris", `None` for an ordinary lock.
+    pub fn label(&self) -> Option<String> {
+        let lines = match self.lines {
+            0 => "",
+            1 => "Single",
+            2 => "Double",
+            3 => "Triple",
+            _ => "Tetris",
+        };
+
+        let name = match (self.spin, self.lines) {
+            (Spin::None, 0) => return None,
+            (Spin::None, _) => lines.to_string(),
+            (Spin::Mini, 0) => "T-Spin Mini".to_string(),
+            (Spin::Mini, _) => format!("T-Spin Mini {}", lines),
+            (Spin::Full, 0) => "T-Spin".to_string(),
+            (Spin::Full, _) => format!("T-Spin {}", lines),
+        };
+
+        let mut label = if self.back_to_back {
+            format!("Back-to-Back {}", name)
+        } else {
+            name
+        };
+
+        if self.combo > 0 {
+            label.push_str(&format!(" Combo {}", self.combo));
+        }
+
+        if self.perfect_clear {
+            label.push_str(" Perfect Clear");
+        }
+
+        Some(label)
+    }
+}
+
+/// Running back-to-back and combo state between locks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Scoring {
+    pub back_to_back: bool,
+    pub combo: Option<u32>,
+}
+
+impl Scoring {
+    /// Scores a lock that cleared `lines` rows at `level`.
+    pub fn score_lock(
+        &mut self,
+        lines: u32,
+        spin: Spin,
+        perfect_clear: bool,
+        level: u32,
+    ) -> ScoreEvent {
+        let multiplier = level.saturating_add(1);
+        let mut points = line_clear_points(lines, spin);
+        let mut back_to_back = false;
+
+        if lines > 0 {
+            let difficult = lines >= 4 || spin != Spin::None;
+            back_to_back = difficult && self.back_to_back;
+
+            if back_to_back {
+                points = points * 3 / 2;
+            }
+
+            self.back_to_back = difficult;
+        }
+
+        let combo = if lines > 0 {
+            let combo = self.combo.map_or(0, |combo| combo.saturating_add(1));
+            self.combo = Some(combo);
+            points = points.saturating_add(combo.saturating_mul(50));
+            combo
+        } else {
+            self.combo = None;
+            0
+        };
+
+        if perfect_clear {
+            points = points.saturating_add(perfect_clear_points(lines, back_to_back));
+        }
+
+        ScoreEvent {
+            lines,
+            spin,
+            back_to_back,
+            combo,
+            perfect_clear,
+            points: points.saturating_mul(multiplier),
+        }
+    }
+}
+
+pub fn line_clear_points(lines: u32, spin: Spin) -> u32 {
+    match (spin, lines) {
+        (Spin::None, 0) => 0,
+        (Spin::None, 1) => 100,
+        (Spin::None, 2) => 300,
+        (Spin::None, 3) => 500,
+        (Spin::None, n) => n * 200,
+        (Spin::Mini, 0) => 100,
+        (Spin::Mini, 1) => 200,
+        (Spin::Mini, _) => 400,
+        (Spin::Full, n) => 400 * (n + 1),
+    }
+}
+
+fn perfect_clear_points(lines: u32, back_to_back: bool) -> u32 {
+    match lines {
+        0 => 0,
+        1 => 800,
+        2 => 1200,
+        3 => 1800,
+        _ if back_to_back => 3200,
+        _ => 2000,
+    }
+}
+
+pub fn is_perfect_clear(game_space: &Matrix) -> bool {
+    game_space.iter().flatten().all(|cell| cell.is_none())
+}
+
+/// 3-corner T-spin check for a T that was just rotated into place with SRS kick `kick`.
+/// Three occupied corners of its 3x3 box make a T-spin; it is a full one when both corners
+/// on the pointing side are occupied or when the last kick was needed, a mini one otherwise.
+pub fn detect_t_spin(game_space: &Matrix, piece: &ActivePiece, kick: usize) -> Spin {
+    if piece.kind != TetrominoKind::T {
+        return Spin::None;
+    }
+
+    let rows = game_space.len() as isize;
+    let cols = game_space[0].len() as isize;
+
+    let occupied = |row: isize, col: isize| {
+        let row = piece.y + row;
+        let col = piece.x + col;
+
+        if col < 0 || col >= cols || row >= rows {
+            return true;
+        }
+
+        row >= 0 && game_space[row as usize][col as usize].is_some()
+    };
+
+    let top_left = occupied(0, 0);
+    let top_right = occupied(0, 2);
+    let bottom_left = occupied(2, 0);
+    let bottom_right = occupied(2, 2);
+
+    let corners = [top_left, top_right, bottom_left, bottom_right]
+        .iter()
+        .filter(|&&corner| corner)
+        .count();
+
+    if corners < 3 {
+        return Spin::None;
+    }
+
+    let front = match piece.rotation {
+        RotationState::Zero => top_left && top_right,
+        RotationState::Right => top_right && bottom_right,
+        RotationState::Two => bottom_left && bottom_right,
+        RotationState::Left => top_left && bottom_left,
+    };
+
+    if front || kick == LAST_KICK {
+        Spin::Full
+    } else {
+        Spin::Mini
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tetromino::Brick;
+
+    #[test]
+    fn test_line_clear_points_are_multiplied_by_level() {
+        let mut scoring = Scoring::default();
+
+        assert_eq!(scoring.score_lock(1, Spin::None, false, 0).points, 100);
+        scoring.combo = None;
+        assert_eq!(scoring.score_lock(2, Spin::None, false, 2).points, 900);
+        scoring.combo = None;
+        assert_eq!(scoring.score_lock(3, Spin::None, false, 0).points, 500);
+        scoring.combo = None;
+        assert_eq!(scoring.score_lock(4, Spin::None, false, 9).points, 8000);
+    }
+
+    #[test]
+    fn test_t_spin_points() {
+        assert_eq!(line_clear_points(0, Spin::Mini), 100);
+        assert_eq!(line_clear_points(1, Spin::Mini), 200);
+        assert_eq!(line_clear_points(2, Spin::Mini), 400);
+        assert_eq!(line_clear_points(0, Spin::Full), 400);
+        assert_eq!(line_clear_points(1, Spin::Full), 800);
+        assert_eq!(line_clear_points(2, Spin::Full), 1200);
+        assert_eq!(line_clear_points(3, Spin::Full), 1600);
+    }
+
+    #[test]
+    fn test_back_to_back_bonus() {
+        let mut scoring = Scoring::default();
+
+        let first = scoring.score_lock(4, Spin::None, false, 0);
+        assert!(!first.back_to_back);
+        scoring.combo = None;
+
+        // A T-spin without lines neither breaks nor extends the chain.
+        scoring.score_lock(0, Spin::Full, false, 0);
+
+        let second = scoring.score_lock(2, Spin::Full, false, 0);
+        assert!(second.back_to_back);
+        assert_eq!(second.points, 1800);
+        scoring.combo = None;
+
+        scoring.score_lock(1, Spin::None, false, 0);
+        scoring.combo = None;
+
+        let third = scoring.score_lock(4, Spin::None, false, 0);
+        assert!(!third.back_to_back);
+        assert_eq!(third.points, 800);
+    }
+
+    #[test]
+    fn test_combo_counter() {
+        let mut scoring = Scoring::default();
+
+        assert_eq!(scoring.score_lock(1, Spin::None, false, 0).combo, 0);
+
+        let second = scoring.score_lock(1, Spin::None, false, 0);
+        assert_eq!(second.combo, 1);
+        assert_eq!(second.points, 150);
+
+        let third = scoring.score_lock(1, Spin::None, false, 1);
+        assert_eq!(third.combo, 2);
+        assert_eq!(third.points, 400);
+
+        assert_eq!(scoring.score_lock(0, Spin::None, false, 0).points, 0);
+        assert_eq!(scoring.combo, None);
+        assert_eq!(scoring.score_lock(1, Spin::None, false, 0).combo, 0);
+    }
+
+    #[test]
+    fn test_perfect_clear_bonus() {
+        let mut scoring = Scoring::default();
+        assert_eq!(scoring.score_lock(1, Spin::None, true, 0).points, 900);
+
+        let mut scoring = Scoring::default();
+        assert_eq!(scoring.score_lock(4, Spin::None, true, 1).points, 5600);
+
+        let mut scoring = Scoring {
+            back_to_back: true,
+            combo: None,
+        };
+        assert_eq!(
+            scoring.score_lock(4, Spin::None, true, 0).points,
+            1200 + 3200
+        );
+    }
+
+    #[test]
+    fn test_points_saturate_instead_of_overflowing() {
+        let mut scoring = Scoring {
+            back_to_back: true,
+            combo: Some(u32::MAX),
+        };
+        let event = scoring.score_lock(4, Spin::None, true, u32::MAX);
+
+        assert_eq!(event.points, u32::MAX);
+        assert_eq!(event.combo, u32::MAX);
+    }
+
+    #[test]
+    fn test_is_perfect_clear() {
+        let brick = Some(Brick::new(TetrominoKind::O));
+
+        assert!(is_perfect_clear(&vec![vec![None; 4]; 3]));
+        assert!(!is_perfect_clear(&vec![
+            vec![None; 4],
+            vec![None, brick, None, None]
+        ]));
+    }
+
+    #[test]
+    fn test_detect_full_t_spin() {
+        let brick = Some(Brick::new(TetrominoKind::O));
+
+        // T-spin double slot with the T pointing down into it.
+        let game_space: Matrix = vec![
+            vec![None; 5],
+            vec![None, brick, None, None, None],
+            vec![brick, None, None, None, brick],
+            vec![brick, brick, None, brick, brick],
+        ];
+        let piece = ActivePiece::new(TetrominoKind::T, RotationState::Two, 1, 1);
+
+        assert_eq!(detect_t_spin(&game_space, &piece, 0), Spin::Full);
+    }
+
+    #[test]
+    fn test_detect_mini_t_spin() {
+        let brick = Some(Brick::new(TetrominoKind::O));
+
+        // Pointing up against the floor: only the back corners and one front corner are set.
+        let game_space: Matrix = vec![
+            vec![None; 4],
+            vec![brick, None, None, None],
+            vec![None, None, None, None],
+        ];
+        let piece = ActivePiece::new(TetrominoKind::T, RotationState::Zero, 0, 1);
+
+        assert_eq!(detect_t_spin(&game_space, &piece, 1), Spin::Mini);
+        assert_eq!(detect_t_spin(&game_space, &piece, LAST_KICK), Spin::Full);
+    }
+
+    #[test]
+    fn test_no_t_spin() {
+        let game_space: Matrix = vec![vec![None; 5]; 4];
+        let piece = ActivePiece::new(TetrominoKind::T, RotationState::Zero, 1, 0);
+        assert_eq!(detect_t_spin(&game_space, &piece, 0), Spin::None);
+
+        let piece = ActivePiece::new(TetrominoKind::S, RotationState::Zero, 1, 2);
+        assert_eq!(detect_t_spin(&game_space, &piece, 0), Spin::None);
+    }
+
+    #[test]
+    fn test_labels() {
+        let mut scoring = Scoring {
+            back_to_back: true,
+            combo: Some(0),
+        };
+
+        let event = scoring.score_lock(2, Spin::Full, false, 0);
+        assert_eq!(
+            event.label().as_deref(),
+            Some("Back-to-Back T-Spin Double Combo 1")
+        );
+
+        let event = scoring.score_lock(0, Spin::None, false, 0);
+        assert_eq!(event.label(), None);
+    }
+}
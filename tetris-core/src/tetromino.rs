@@ -0,0 +1,205 @@
+use crate::types::Matrix;
+use crate::enums::{RotationState, TetrominoKind};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Brick {
+    pub kind: TetrominoKind,
+    /// Set for bricks of garbage rows rather than of locked pieces. Their `kind` means nothing.
+    pub garbage: bool,
+}
+
+impl Brick {
+    pub fn new(kind: TetrominoKind) -> Self {
+        Brick { kind, garbage: false }
+    }
+
+    pub fn garbage() -> Self {
+        Brick {
+            kind: TetrominoKind::default(),
+            garbage: true,
+        }
+    }
+}
+
+/// Side of the square SRS bounding box the piece rotates in.
+pub fn box_size(kind: TetrominoKind) -> usize {
+    match kind {
+        TetrominoKind::O => 2,
+        TetrominoKind::I => 4,
+        _ => 3,
+    }
+}
+
+type Shape = [[(usize, usize); 4]; 4];
+
+// SRS rotation states in the order 0, R, 2, L, as `(row, col)` inside the bounding box. State 0
+// is the guideline spawn orientation: flat side down, with J, L and T pointing up.
+const O_SHAPE: Shape = [[(0, 0), (0, 1), (1, 0), (1, 1)]; 4];
+
+const I_SHAPE: Shape = [
+    [(1, 0), (1, 1), (1, 2), (1, 3)],
+    [(0, 2), (1, 2), (2, 2), (3, 2)],
+    [(2, 0), (2, 1), (2, 2), (2, 3)],
+    [(0, 1), (1, 1), (2, 1), (3, 1)],
+];
+
+const S_SHAPE: Shape = [
+    [(0, 1), (0, 2), (1, 0), (1, 1)],
+    [(0, 1), (1, 1), (1, 2), (2, 2)],
+    [(1, 1), (1, 2), (2, 0), (2, 1)],
+    [(0, 0), (1, 0), (1, 1), (2, 1)],
+];
+
+const Z_SHAPE: Shape = [
+    [(0, 0), (0, 1), (1, 1), (1, 2)],
+    [(0, 2), (1, 1), (1, 2), (2, 1)],
+    [(1, 0), (1, 1), (2, 1), (2, 2)],
+    [(0, 1), (1, 0), (1, 1), (2, 0)],
+];
+
+const T_SHAPE: Shape = [
+    [(0, 1), (1, 0), (1, 1), (1, 2)],
+    [(0, 1), (1, 1), (1, 2), (2, 1)],
+    [(1, 0), (1, 1), (1, 2), (2, 1)],
+    [(0, 1), (1, 0), (1, 1), (2, 1)],
+];
+
+const L_SHAPE: Shape = [
+    [(0, 2), (1, 0), (1, 1), (1, 2)],
+    [(0, 1), (1, 1), (2, 1), (2, 2)],
+    [(1, 0), (1, 1), (1, 2), (2, 0)],
+    [(0, 0), (0, 1), (1, 1), (2, 1)],
+];
+
+const J_SHAPE: Shape = [
+    [(0, 0), (1, 0), (1, 1), (1, 2)],
+    [(0, 1), (0, 2), (1, 1), (2, 1)],
+    [(1, 0), (1, 1), (1, 2), (2, 2)],
+    [(0, 1), (1, 1), (2, 0), (2, 1)],
+];
+
+/// Cells of the piece as `(row, col)` inside its bounding box for the given rotation state.
+/// This is the one definition of the piece shapes; movement, rotation, spawning and the
+/// preview all read it.
+pub fn shape_cells(kind: TetrominoKind, rotation: RotationState) -> [(usize, usize); 4] {
+    let shape = match kind {
+        TetrominoKind::O => &O_SHAPE,
+        TetrominoKind::I => &I_SHAPE,
+        TetrominoKind::S => &S_SHAPE,
+        TetrominoKind::Z => &Z_SHAPE,
+        TetrominoKind::T => &T_SHAPE,
+        TetrominoKind::L => &L_SHAPE,
+        TetrominoKind::J => &J_SHAPE,
+    };
+
+    shape[rotation as usize]
+}
+
+/// A piece inside its SRS bounding box (2x2 for O, 4x4 for I, 3x3 for the rest).
+/// `rotation` is the SRS state the `matrix` is currently drawn in.
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct Tetromino {
+    pub kind: TetrominoKind,
+    pub rotation: RotationState,
+    pub matrix: Matrix,
+}
+
+impl Tetromino {
+    pub fn new(kind: TetrominoKind, rotation: RotationState, matrix: Matrix) -> Self {
+        Self {
+            kind,
+            rotation,
+            matrix,
+        }
+    }
+
+    /// The piece in the given rotation state, drawn from [`shape_cells`].
+    pub fn with_rotation(kind: TetrominoKind, rotation: RotationState) -> Self {
+        let size = box_size(kind);
+        let mut matrix: Matrix = vec![vec![None; size]; size];
+
+        for (row, col) in shape_cells(kind, rotation) {
+            matrix[row][col] = Some(Brick::new(kind));
+        }
+
+        Self::new(kind, rotation, matrix)
+    }
+
+    /// The piece in its spawn orientation.
+    pub fn from_kind(kind: TetrominoKind) -> Self {
+        Self::with_rotation(kind, RotationState::Zero)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix_cells(item: &Tetromino) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+
+        for (row_index, row) in item.matrix.iter().enumerate() {
+            for (col_index, cell) in row.iter().enumerate() {
+                if cell.is_some() {
+                    cells.push((row_index, col_index));
+                }
+            }
+        }
+
+        cells
+    }
+
+    #[test]
+    fn test_matrix_matches_shape_cells() {
+        for kind in TetrominoKind::ALL {
+            for rotation in [RotationState::Zero, RotationState::Right, RotationState::Two, RotationState::Left] {
+                let item = Tetromino::with_rotation(kind, rotation);
+                let mut cells = shape_cells(kind, rotation).to_vec();
+                cells.sort();
+
+                assert_eq!(cells, matrix_cells(&item), "{:?} {:?}", kind, rotation);
+                assert_eq!(item.matrix.len(), box_size(kind), "{:?}", kind);
+            }
+        }
+    }
+
+    #[test]
+    fn test_spawn_orientation_is_flat_side_down() {
+        for kind in TetrominoKind::ALL {
+            let item = Tetromino::from_kind(kind);
+            let filled: Vec<usize> = item
+                .matrix
+                .iter()
+                .map(|row| row.iter().filter(|cell| cell.is_some()).count())
+                .filter(|&count| count > 0)
+                .collect();
+
+            assert_eq!(item.rotation, RotationState::Zero);
+            assert!(filled.len() <= 2, "{:?} is not flat", kind);
+            assert!(filled.last() >= filled.first(), "{:?} is not flat side down", kind);
+        }
+    }
+
+    #[test]
+    fn test_each_state_is_a_quarter_turn_of_the_previous() {
+        for kind in TetrominoKind::ALL {
+            let last = box_size(kind) - 1;
+            let mut rotation = RotationState::Zero;
+
+            for _ in 0..4 {
+                let mut turned: Vec<(usize, usize)> = shape_cells(kind, rotation)
+                    .iter()
+                    .map(|&(row, col)| (col, last - row))
+                    .collect();
+                turned.sort();
+
+                rotation = rotation.clockwise();
+
+                let mut expected = shape_cells(kind, rotation).to_vec();
+                expected.sort();
+
+                assert_eq!(turned, expected, "{:?} into {:?}", kind, rotation);
+            }
+        }
+    }
+}
@@ -0,0 +1,4 @@
+use crate::tetromino::{Brick, Tetromino};
+
+pub type Matrix = Vec<Vec<Option<Brick>>>;
+pub type Tetrominos = Vec<Tetromino>;